@@ -6,3 +6,24 @@ mod gpu {
 
     fn func_with_invalid_param((a, b): (F32, F32)) {}
 }
+
+#[ragna::gpu]
+mod shaders {
+    use ragna::{F32x4, U32};
+
+    #[vertex]
+    fn vertex_without_return(vertex_index: U32, instance_index: U32) {}
+
+    #[vertex]
+    fn vertex_with_invalid_param_count(vertex_index: U32) -> F32x4 {
+        F32x4::new(0., 0., 0., 1.)
+    }
+
+    #[fragment]
+    fn fragment_without_return(position: F32x4) {}
+
+    #[fragment]
+    fn fragment_with_invalid_param_count(position: F32x4, other: F32x4) -> F32x4 {
+        position
+    }
+}
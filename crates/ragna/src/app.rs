@@ -1,18 +1,26 @@
 use crate::context::GpuContext;
-use crate::operations::{AssignVarOperation, Operation};
+use crate::draw::{CustomDraw, Draw, OffscreenTarget};
+use crate::operations::{self, AssignVarOperation, ConstantValue, Operation};
 use crate::runner::common::Runner;
 use crate::runner::window::WindowRunner;
-use crate::types::GpuTypeDetails;
-use crate::{wgsl, Cpu, Glob, Gpu, GpuValue};
+use crate::texture::TextureSource;
+use crate::types::{self, GpuTypeDetails};
+use crate::{
+    camera, input, post_process, snapshot, target, time, wgsl, Array, Cpu, DrawConfig, F32x4, Glob,
+    GlobSnapshot, Gpu, GpuConfig, GpuTuple, GpuValue, Image, InputEvent, OffscreenDraws,
+    PendingRead, RagnaError, SamplerConfig, Shape, Sprite, StepStats, Text, Texture, TextureCpu,
+    TextureTargetConfig, TextureTargetFormat, U32,
+};
 use derive_where::derive_where;
 use fxhash::FxHashMap;
 use std::any::TypeId;
 use std::mem;
-use std::sync::Mutex;
+use std::ops::Range;
+use std::time::Duration;
 use wgpu::Color;
 use winit::event_loop::EventLoop;
 
-pub(crate) static CURRENT_CTX: Mutex<Option<GpuContext>> = Mutex::new(None);
+const MIN_UPLOADED_CONSTANT_SIZE: usize = 1024;
 
 /// The entrypoint of a Ragna application.
 #[derive(Default)]
@@ -21,26 +29,337 @@ pub struct App {
     pub(crate) contexts: Vec<GpuContext>,
     pub(crate) globs: Vec<GpuValue>,
     #[derive_where(skip)]
-    pub(crate) glob_defaults: Vec<Box<dyn Fn() -> GpuValue>>,
+    pub(crate) glob_defaults: Vec<Box<dyn Fn() -> GpuValue + Sync + Send>>,
+    #[derive_where(skip)]
+    constants: FxHashMap<(TypeId, Vec<u8>), GpuValue>,
     pub(crate) types: FxHashMap<TypeId, (usize, GpuTypeDetails)>,
+    pub(crate) gpu_config: GpuConfig,
+    pub(crate) draws: Vec<(Draw, DrawConfig)>,
+    pub(crate) post_processes: Vec<Draw>,
+    pub(crate) textures: Vec<(TextureSource, SamplerConfig)>,
+    pub(crate) offscreen_targets: Vec<OffscreenTarget>,
 }
 
 impl App {
     /// Configure the application to run with a texture target.
     ///
     /// `size` corresponds to the width and height of the texture target.
+    ///
+    /// # Panics
+    ///
+    /// This will panic if the application cannot be initialized.
+    /// See [`App::try_texture`] for more details.
     pub fn texture(self, size: (u32, u32)) -> TextureApp {
-        let runner = Runner::new_texture(&self, size);
-        TextureApp { app: self, runner }
+        self.try_texture(size)
+            .expect("cannot initialize texture application")
+    }
+
+    /// Configure the application to run with a texture target using a specific configuration.
+    ///
+    /// `size` corresponds to the width and height of the texture target.
+    ///
+    /// # Panics
+    ///
+    /// This will panic if the application cannot be initialized.
+    /// See [`App::try_texture_with`] for more details.
+    pub fn texture_with(self, size: (u32, u32), config: TextureTargetConfig) -> TextureApp {
+        self.try_texture_with(size, config)
+            .expect("cannot initialize texture application")
+    }
+
+    /// Configure the application to run with a texture target.
+    ///
+    /// `size` corresponds to the width and height of the texture target.
+    ///
+    /// # Errors
+    ///
+    /// An error is returned if no graphic adapter or device can be retrieved, if a generated shader
+    /// is invalid or if the global variables don't fit in a GPU buffer.
+    pub fn try_texture(self, size: (u32, u32)) -> Result<TextureApp, RagnaError> {
+        self.try_texture_with(size, TextureTargetConfig::default())
+    }
+
+    /// Configure the application to run with a texture target using a specific configuration.
+    ///
+    /// `size` corresponds to the width and height of the texture target.
+    ///
+    /// # Errors
+    ///
+    /// An error is returned if no graphic adapter or device can be retrieved, if the number of
+    /// samples per pixel is not supported, if a generated shader is invalid or if the global
+    /// variables don't fit in a GPU buffer.
+    pub fn try_texture_with(
+        self,
+        size: (u32, u32),
+        config: TextureTargetConfig,
+    ) -> Result<TextureApp, RagnaError> {
+        let app = self.with_builtin_globs();
+        let runner = Runner::new_texture(&app, size, config)?;
+        Ok(TextureApp {
+            app,
+            runner,
+            format: config.format,
+        })
     }
 
     // coverage: off (window cannot be tested)
     /// Configure the application to run with a window.
     pub fn window(self) -> WindowApp {
-        WindowApp { app: self }
+        WindowApp {
+            app: self.with_builtin_globs(),
+            step_callback: None,
+        }
     }
+
     // coverage: on
 
+    /// Sets the configuration used to select the GPU adapter and device.
+    pub fn with_gpu_config(mut self, config: GpuConfig) -> Self {
+        self.gpu_config = config;
+        self
+    }
+
+    /// Draws at each step the shapes stored in a global variable.
+    ///
+    /// The global variable is registered if it is not already the case.
+    ///
+    /// Draws are run in the order they are registered, after all compute functions.
+    pub fn with_shapes<const N: usize>(self, shapes: &Glob<Array<Shape, N>>) -> Self {
+        self.with_shapes_config(shapes, DrawConfig::default())
+    }
+
+    /// Draws at each step the shapes stored in a global variable using a specific configuration.
+    ///
+    /// See [`App::with_shapes`] for more details.
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn with_shapes_config<const N: usize>(
+        self,
+        shapes: &Glob<Array<Shape, N>>,
+        config: DrawConfig,
+    ) -> Self {
+        let mut app = self.with_glob(shapes);
+        app.draws.push((
+            Draw::Shapes {
+                shapes: shapes.value(),
+                count: N as u32,
+            },
+            config,
+        ));
+        app
+    }
+
+    /// Draws at each step the sprites stored in a global variable.
+    ///
+    /// The global variable is registered if it is not already the case.
+    ///
+    /// Draws are run in the order they are registered, after all compute functions.
+    pub fn with_sprites<const N: usize>(self, sprites: &Glob<Array<Sprite, N>>) -> Self {
+        self.with_sprites_config(sprites, DrawConfig::default())
+    }
+
+    /// Draws at each step the sprites stored in a global variable using a specific configuration.
+    ///
+    /// See [`App::with_sprites`] for more details.
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn with_sprites_config<const N: usize>(
+        self,
+        sprites: &Glob<Array<Sprite, N>>,
+        config: DrawConfig,
+    ) -> Self {
+        let mut app = self.with_glob(sprites);
+        app.draws.push((
+            Draw::Sprites {
+                sprites: sprites.value(),
+                count: N as u32,
+            },
+            config,
+        ));
+        app
+    }
+
+    /// Draws at each step the text stored in a global variable.
+    ///
+    /// The global variable is registered if it is not already the case.
+    ///
+    /// Draws are run in the order they are registered, after all compute functions.
+    pub fn with_text<const N: usize>(self, text: &Glob<Text<N>>) -> Self {
+        self.with_text_config(text, DrawConfig::default())
+    }
+
+    /// Draws at each step the text stored in a global variable using a specific configuration.
+    ///
+    /// See [`App::with_text`] for more details.
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn with_text_config<const N: usize>(
+        self,
+        text: &Glob<Text<N>>,
+        config: DrawConfig,
+    ) -> Self {
+        let mut app = self.with_glob(text);
+        app.draws.push((
+            Draw::Text {
+                text: text.value(),
+                capacity: N as u32,
+            },
+            config,
+        ));
+        app
+    }
+
+    /// Draws at each step primitives defined by a vertex function and a fragment function.
+    ///
+    /// `vertex` is a function annotated with `#[vertex]` that takes the vertex index and the
+    /// instance index, and returns the value passed to `fragment`. If this value is a struct, its
+    /// first field is the vertex position in normalized device coordinates and the other fields
+    /// are interpolated. Else, the value is the vertex position.
+    ///
+    /// `fragment` is a function annotated with `#[fragment]` that returns the RGBA color of the
+    /// pixel.
+    ///
+    /// Each group of three vertices forms a triangle. `vertex_count` vertices are generated for
+    /// each of the `instance_count` instances.
+    ///
+    /// Global variables can be read but not modified by these functions.
+    pub fn with_draw<T: Gpu>(
+        self,
+        vertex: impl FnOnce(U32, U32) -> T,
+        fragment: impl FnOnce(T) -> F32x4,
+        vertex_count: u32,
+        instance_count: u32,
+    ) -> Self {
+        self.with_draw_config(
+            vertex,
+            fragment,
+            vertex_count,
+            instance_count,
+            DrawConfig::default(),
+        )
+    }
+
+    /// Draws at each step primitives defined by a vertex function and a fragment function using a
+    /// specific configuration.
+    ///
+    /// See [`App::with_draw`] for more details.
+    pub fn with_draw_config<T: Gpu>(
+        mut self,
+        vertex: impl FnOnce(U32, U32) -> T,
+        fragment: impl FnOnce(T) -> F32x4,
+        vertex_count: u32,
+        instance_count: u32,
+        config: DrawConfig,
+    ) -> Self {
+        let mut draw = CustomDraw::new(vertex, fragment, vertex_count, instance_count);
+        self.add_context(&mut draw.vertex_ctx);
+        self.add_context(&mut draw.fragment_ctx);
+        self.draws.push((Draw::Custom(draw), config));
+        self
+    }
+
+    /// Applies at each step a full-screen post-process to the target.
+    ///
+    /// `fragment` is a function annotated with `#[fragment]` that takes the fragment position and
+    /// returns the RGBA color of the pixel. The `x` and `y` components of the fragment position
+    /// are the pixel position, with the origin at the top-left corner of the target.
+    ///
+    /// The color of the target before the post-process is retrieved with
+    /// [`source_color`](crate::source_color). Built-in effects like [`blur`](crate::blur),
+    /// [`vignette`](crate::vignette) or [`grade_color`](crate::grade_color) can be used in the
+    /// fragment function.
+    ///
+    /// Post-processes are run in the order they are registered, after all draws.
+    pub fn with_post_process(mut self, fragment: impl FnOnce(F32x4) -> F32x4) -> Self {
+        let mut draw = CustomDraw::new(post_process::fullscreen_vertex, fragment, 3, 1);
+        self.add_context(&mut draw.vertex_ctx);
+        self.add_context(&mut draw.fragment_ctx);
+        self.post_processes.push(Draw::PostProcess(draw));
+        self
+    }
+
+    /// Loads an image in a GPU texture referred by a global variable.
+    ///
+    /// The global variable is registered if it is not already the case, and its default value
+    /// is replaced by a handle referring to the texture.
+    ///
+    /// The texture can then be sampled with [`sample`](crate::sample) in any GPU function.
+    pub fn with_texture(
+        self,
+        texture: &Glob<Texture>,
+        image: Image,
+        sampler: SamplerConfig,
+    ) -> Self {
+        self.with_texture_source(texture, TextureSource::Image(image), sampler)
+    }
+
+    /// Renders at each step draws in an offscreen texture referred by a global variable.
+    ///
+    /// `name` identifies the target, e.g. to read it with
+    /// [`TextureApp::read_texture`](crate::TextureApp::read_texture).
+    /// `size` corresponds to the width and height of the texture.
+    ///
+    /// The draws registered by `draws` (e.g. with [`OffscreenDraws::with_shapes`] or
+    /// [`OffscreenDraws::with_draw`]) are rendered in the offscreen texture instead of the main
+    /// target. They use the same
+    /// coordinates as the main target, scaled to the offscreen texture size. The texture is
+    /// cleared with a transparent color before each step.
+    ///
+    /// The global variable is registered if it is not already the case, and its default value
+    /// is replaced by a handle referring to the texture. The texture can then be sampled with
+    /// [`sample`](crate::sample) by compute functions, by draws of the main target and by draws of
+    /// offscreen targets registered later. Offscreen targets are rendered in the order they are
+    /// registered, before draws of the main target.
+    ///
+    /// # Panics
+    ///
+    /// This will panic if an offscreen target with the same name is already registered.
+    pub fn with_offscreen_target(
+        self,
+        name: &str,
+        texture: &Glob<Texture>,
+        size: (u32, u32),
+        sampler: SamplerConfig,
+        draws: impl FnOnce(OffscreenDraws) -> OffscreenDraws,
+    ) -> Self {
+        assert!(
+            self.offscreen_targets
+                .iter()
+                .all(|target| target.name != name),
+            "offscreen target `{name}` already registered"
+        );
+        let size = (size.0.max(1), size.1.max(1));
+        let mut app =
+            self.with_texture_source(texture, TextureSource::OffscreenTarget { size }, sampler);
+        let texture_index = app.textures.len() - 1;
+        let main_draws = mem::take(&mut app.draws);
+        let mut app = draws(OffscreenDraws { app }).app;
+        let draws = mem::replace(&mut app.draws, main_draws);
+        app.offscreen_targets.push(OffscreenTarget {
+            name: name.into(),
+            texture_index,
+            size,
+            draws,
+        });
+        app
+    }
+
+    #[allow(clippy::cast_possible_truncation)]
+    fn with_texture_source(
+        self,
+        texture: &Glob<Texture>,
+        source: TextureSource,
+        sampler: SamplerConfig,
+    ) -> Self {
+        let mut app = self.with_glob(texture);
+        let index = app.textures.len() as u32;
+        let position = app
+            .globs
+            .iter()
+            .position(|glob| glob == &texture.value())
+            .expect("internal error: texture glob not registered");
+        app.glob_defaults[position] = Box::new(move || TextureCpu { index }.to_gpu().value());
+        app.textures.push((source, sampler));
+        app
+    }
+
     /// Registers a GPU module.
     pub fn with_module(mut self, f: impl FnOnce(Self) -> Self) -> Self {
         f(mem::take(&mut self))
@@ -48,53 +367,85 @@ impl App {
 
     #[doc(hidden)]
     pub fn with_compute(mut self, f: impl FnOnce()) -> Self {
-        let lock = GpuContext::lock_current();
-        f();
-        let mut ctx = GpuContext::unlock_current(lock);
-        for type_ in mem::take(&mut ctx.types) {
-            self.add_type(type_);
-        }
+        let mut ctx = GpuContext::record(f);
+        self.add_context(&mut ctx);
         self.contexts.push(ctx);
         self
     }
 
     #[doc(hidden)]
     pub fn with_glob<T: Gpu>(mut self, glob: &Glob<T>) -> Self {
+        if self.globs.contains(&glob.inner.value()) {
+            return self;
+        }
         let default_value = glob.default_value;
         self.glob_defaults
             .push(Box::new(move || default_value().value()));
         self.globs.push(glob.inner.value());
-        let lock = GpuContext::lock_current();
-        GpuContext::run_current(GpuContext::register_type::<T>);
-        let mut ctx = GpuContext::unlock_current(lock);
+        let mut ctx = GpuContext::default();
+        ctx.register_type::<T>();
         for type_ in mem::take(&mut ctx.types) {
             self.add_type(type_);
         }
         self
     }
 
-    pub(crate) fn wgsl_init_shader(&self) -> String {
-        let lock = GpuContext::lock_current();
-        for (glob, default_value) in self.globs.iter().zip(&self.glob_defaults) {
-            let right_value = default_value();
-            GpuContext::run_current(|ctx| {
-                ctx.operations
-                    .push(Operation::AssignVar(AssignVarOperation {
-                        left_value: *glob,
-                        right_value,
-                    }));
-            });
-        }
-        let ctx = GpuContext::unlock_current(lock);
-        format!(
+    fn with_builtin_globs(self) -> Self {
+        self.with_glob(&time::DELTA_SECONDS)
+            .with_glob(&time::ELAPSED_SECONDS)
+            .with_glob(&time::STEP_INDEX)
+            .with_glob(&input::PRESSED_KEYS)
+            .with_glob(&input::JUST_PRESSED_KEYS)
+            .with_glob(&input::JUST_RELEASED_KEYS)
+            .with_glob(&input::PRESSED_MOUSE_BUTTONS)
+            .with_glob(&input::JUST_PRESSED_MOUSE_BUTTONS)
+            .with_glob(&input::JUST_RELEASED_MOUSE_BUTTONS)
+            .with_glob(&input::MOUSE_POSITION)
+            .with_glob(&input::MOUSE_WHEEL_DELTA)
+            .with_glob(&target::TARGET_SIZE)
+            .with_glob(&target::SCALE_FACTOR)
+            .with_glob(&camera::CAMERA)
+    }
+
+    // default values known on CPU side are returned as bytes instead of being embedded in the shader
+    pub(crate) fn wgsl_init_shader(&self) -> (String, Vec<(GpuValue, Vec<u8>)>) {
+        let mut constant_globs: Vec<_> = self
+            .constants
+            .iter()
+            .map(|((_, bytes), glob)| (*glob, bytes.clone()))
+            .collect();
+        let ctx = GpuContext::record(|| {
+            for (glob, default_value) in self.globs.iter().zip(&self.glob_defaults) {
+                let first_operation = GpuContext::run_current(|ctx| ctx.operations.len());
+                let right_value = default_value();
+                GpuContext::run_current(|ctx| {
+                    let operations = &ctx.operations[first_operation..];
+                    if let Some(bytes) =
+                        operations::constant_bytes(operations, &right_value, &self.types)
+                    {
+                        ctx.operations.truncate(first_operation);
+                        constant_globs.push((*glob, bytes));
+                    } else if right_value != *glob {
+                        // globs defaulting to themselves are written on CPU side
+                        ctx.operations
+                            .push(Operation::AssignVar(AssignVarOperation {
+                                left_value: *glob,
+                                right_value,
+                            }));
+                    }
+                });
+            }
+        });
+        let code = format!(
             "{}{}",
-            wgsl::header_code(&self.types, &self.globs),
+            self.compute_header_code(),
             wgsl::compute_shader_code(&ctx, &self.types, &self.globs)
-        )
+        );
+        (code, constant_globs)
     }
 
     pub(crate) fn wgsl_update_shaders(&self) -> impl Iterator<Item = String> + '_ {
-        let header = wgsl::header_code(&self.types, &self.globs);
+        let header = self.compute_header_code();
         self.contexts.iter().map(move |ctx| {
             format!(
                 "{}{}",
@@ -104,6 +455,95 @@ impl App {
         })
     }
 
+    pub(crate) fn is_using_pixels(&self) -> bool {
+        self.contexts.iter().any(|ctx| {
+            ctx.operations
+                .iter()
+                .any(|operation| matches!(operation, Operation::SetPixel(_)))
+        })
+    }
+
+    pub(crate) fn wgsl_draw_shaders(
+        &self,
+    ) -> impl Iterator<Item = (String, &(Draw, DrawConfig))> + '_ {
+        self.wgsl_render_shaders(&self.draws, |(draw, _)| draw)
+    }
+
+    pub(crate) fn wgsl_post_process_shaders(&self) -> impl Iterator<Item = (String, &Draw)> + '_ {
+        self.wgsl_render_shaders(&self.post_processes, |draw| draw)
+    }
+
+    pub(crate) fn wgsl_offscreen_shaders(
+        &self,
+        target: usize,
+    ) -> impl Iterator<Item = (String, &(Draw, DrawConfig))> + '_ {
+        self.wgsl_render_shaders(&self.offscreen_targets[target].draws, |(draw, _)| draw)
+    }
+
+    fn wgsl_render_shaders<'a, T>(
+        &'a self,
+        items: &'a [T],
+        draw: impl Fn(&T) -> &Draw + 'a,
+    ) -> impl Iterator<Item = (String, &'a T)> + 'a {
+        let header = format!(
+            "{}{}{}",
+            wgsl::render_header_code(&self.types, &self.globs),
+            wgsl::texture_header_code(self.textures.len()),
+            wgsl::camera_header_code(&self.globs)
+        );
+        items.iter().map(move |item| {
+            let code = draw(item).wgsl_code(&self.types, &self.globs);
+            (format!("{header}{code}"), item)
+        })
+    }
+
+    fn compute_header_code(&self) -> String {
+        let header = wgsl::header_code(&self.types, &self.globs);
+        let texture_header = wgsl::texture_header_code(self.textures.len());
+        let camera_header = wgsl::camera_header_code(&self.globs);
+        if self.is_using_pixels() {
+            format!(
+                "{header}{}{texture_header}{camera_header}",
+                wgsl::pixel_header_code()
+            )
+        } else {
+            format!("{header}{texture_header}{camera_header}")
+        }
+    }
+
+    // large constants are uploaded in hidden globs instead of being embedded in the shader
+    fn add_context(&mut self, ctx: &mut GpuContext) {
+        for type_ in mem::take(&mut ctx.types) {
+            self.add_type(type_);
+        }
+        for operation in &mut ctx.operations {
+            let Operation::ConstantAssignVar(op) = operation else {
+                continue;
+            };
+            let ConstantValue::Cpu { bytes, .. } = &mut op.right_value else {
+                continue;
+            };
+            if bytes.len() < MIN_UPLOADED_CONSTANT_SIZE {
+                continue;
+            }
+            // identical constants, e.g. of a function called several times, are uploaded once
+            let constant_index = self.constants.len();
+            let glob = *self
+                .constants
+                .entry((op.left_value.type_id, mem::take(bytes)))
+                .or_insert_with(|| {
+                    let glob = GpuValue::constant(constant_index, op.left_value.type_id);
+                    self.globs.push(glob);
+                    self.glob_defaults.push(Box::new(move || glob));
+                    glob
+                });
+            *operation = Operation::AssignVar(AssignVarOperation {
+                left_value: op.left_value,
+                right_value: glob,
+            });
+        }
+    }
+
     pub(crate) fn add_type(&mut self, type_: GpuTypeDetails) {
         let type_count = self.types.len();
         self.types
@@ -115,31 +555,107 @@ impl App {
 // coverage: off (window cannot be tested)
 
 /// An application run with a window.
-#[derive(Debug)]
+#[derive_where(Debug)]
 pub struct WindowApp {
     app: App,
+    #[derive_where(skip)]
+    step_callback: Option<StepCallback>,
 }
 
+pub(crate) type StepCallback = Box<dyn FnMut(&mut WindowContext<'_>)>;
+
 impl WindowApp {
+    /// Sets a function called after each step.
+    pub fn on_step(mut self, f: impl FnMut(&mut WindowContext<'_>) + 'static) -> Self {
+        self.step_callback = Some(Box::new(f));
+        self
+    }
+
     /// Runs the application with a window.
     ///
     /// `background_color` corresponds to RGBA components between `0.0` and `1.0`
     /// of the applied background color.
     pub fn run(self, background_color: (f64, f64, f64, f64)) {
+        self.try_run(background_color)
+            .expect("cannot initialize window application");
+    }
+
+    /// Runs the application with a window, returning an error if it cannot be initialized.
+    ///
+    /// `background_color` corresponds to RGBA components between `0.0` and `1.0`
+    /// of the applied background color.
+    ///
+    /// # Errors
+    ///
+    /// An error is returned if no graphic adapter or device can be retrieved, if a generated shader
+    /// is invalid or if the global variables don't fit in a GPU buffer.
+    pub fn try_run(self, background_color: (f64, f64, f64, f64)) -> Result<(), RagnaError> {
         let event_loop = EventLoop::builder()
             .build()
             .expect("event loop initialization failed");
-        event_loop
-            .run_app(&mut WindowRunner::new(
-                self.app,
-                Color {
-                    r: background_color.0,
-                    g: background_color.1,
-                    b: background_color.2,
-                    a: background_color.3,
-                },
-            ))
-            .expect("event loop failed");
+        let mut runner = WindowRunner::new(
+            self.app,
+            Color {
+                r: background_color.0,
+                g: background_color.1,
+                b: background_color.2,
+                a: background_color.3,
+            },
+            self.step_callback,
+        );
+        event_loop.run_app(&mut runner).expect("event loop failed");
+        runner.error.map_or(Ok(()), Err)
+    }
+}
+
+/// The state of an application run with a window, accessible between steps.
+#[derive(Debug)]
+pub struct WindowContext<'a> {
+    pub(crate) app: &'a App,
+    pub(crate) runner: &'a mut Runner,
+}
+
+impl WindowContext<'_> {
+    /// Returns statistics about the run steps.
+    pub fn stats(&self) -> StepStats {
+        self.runner.stats
+    }
+
+    /// Writes a value in a global variable stored on GPU side.
+    ///
+    /// The value is taken into account from the next step.
+    ///
+    /// If the passed global variable is not registered, nothing is written.
+    pub fn write<T: Gpu>(&mut self, glob: T, value: &T::Cpu) {
+        let bytes = types::gpu_bytes(value);
+        self.runner.write(&glob.value(), &bytes);
+    }
+
+    /// Reads a value stored on GPU side without blocking the calling thread.
+    ///
+    /// The returned read can be kept between steps and polled with
+    /// [`PendingRead::try_read`], which avoids stalling the rendering of the window.
+    /// It resolves to `None` if the passed value is not a global variable.
+    pub fn read_async<T: Gpu>(&self, value: T) -> PendingRead<Option<T::Cpu>> {
+        read_async(self.runner, self.app, value)
+    }
+
+    /// Writes items in an array stored on GPU side, starting at index `start`.
+    ///
+    /// The items are taken into account from the next step.
+    ///
+    /// If the passed array is not a global variable, nothing is written.
+    ///
+    /// # Panics
+    ///
+    /// This will panic if the written items are out of the array bounds.
+    pub fn write_range<T: Gpu, const N: usize>(
+        &mut self,
+        array: Array<T, N>,
+        start: usize,
+        items: &[T::Cpu],
+    ) {
+        write_array_range(self.runner, array, start, items);
     }
 }
 
@@ -150,6 +666,7 @@ impl WindowApp {
 pub struct TextureApp {
     app: App,
     runner: Runner,
+    format: TextureTargetFormat,
 }
 
 impl TextureApp {
@@ -168,22 +685,189 @@ impl TextureApp {
     ///
     /// If the passed value is not a global variable,
     pub fn read<T: Gpu>(&self, value: T) -> Option<T::Cpu> {
-        let bytes = self.runner.read(&self.app, &value.value());
-        if bytes.is_empty() {
-            None
-        } else {
-            Some(Cpu::from_gpu(&bytes))
+        snapshot::read_value::<T>(&self.runner.read(&self.app, &value.value()))
+    }
+
+    /// Reads a value stored on GPU side without blocking the calling thread.
+    ///
+    /// The returned read can be awaited, or polled with [`PendingRead::try_read`].
+    /// It resolves to `None` if the passed value is not a global variable.
+    pub fn read_async<T: Gpu>(&self, value: T) -> PendingRead<Option<T::Cpu>> {
+        read_async(&self.runner, &self.app, value)
+    }
+
+    /// Reads a range of items of an array stored on GPU side.
+    ///
+    /// Only the bytes of the items in `range` are retrieved from the GPU, which is faster than
+    /// reading the whole array with [`TextureApp::read`] for large arrays.
+    ///
+    /// If the passed array is not a global variable, `None` is returned.
+    ///
+    /// # Panics
+    ///
+    /// This will panic if `range` is out of the array bounds.
+    pub fn read_range<T: Gpu, const N: usize>(
+        &self,
+        array: Array<T, N>,
+        range: Range<usize>,
+    ) -> Option<Vec<T::Cpu>> {
+        assert!(
+            range.start <= range.end && range.end <= N,
+            "range {range:?} out of bounds for array of length {N}"
+        );
+        let stride = Array::<T, N>::item_stride();
+        let bytes = self.runner.read_range(
+            &array.value(),
+            range.start as u64 * stride,
+            range.len() as u64 * stride,
+        )?;
+        Some(Array::<T, N>::items_from_gpu(&bytes))
+    }
+
+    /// Reads a tuple of values stored on GPU side.
+    ///
+    /// The values are retrieved in a single GPU round-trip, which is faster than calling
+    /// [`TextureApp::read`] for each value.
+    ///
+    /// Each value that is not a global variable registered in the application is read as `None`.
+    pub fn read_many<T: GpuTuple>(&self, values: T) -> T::Cpu {
+        T::from_gpu(&self.runner.read_many(&self.app, &values.values()))
+    }
+
+    /// Reads all global variables stored on GPU side in a single GPU round-trip.
+    pub fn read_all(&self) -> GlobSnapshot {
+        GlobSnapshot {
+            bytes: self.runner.read_globs(),
+            globs: self.app.globs.clone(),
+            details: GpuTypeDetails::from_fields(&self.app.globs, &self.app.types),
         }
     }
 
+    /// Returns statistics about the run steps.
+    pub fn stats(&self) -> StepStats {
+        self.runner.stats
+    }
+
     /// Reads texture target stored on GPU side.
+    ///
+    /// The data are returned in the format of the target.
     pub fn read_target(&self) -> TextureData {
         TextureData {
             buffer: self.runner.read_target(),
             size: self.runner.target.config.size,
+            format: self.format,
         }
     }
 
+    /// Reads texture target stored on GPU side without blocking the calling thread.
+    ///
+    /// The returned read can be awaited, or polled with [`PendingRead::try_read`].
+    pub fn read_target_async(&self) -> PendingRead<TextureData> {
+        let receiver = self.runner.read_target_async();
+        let size = self.runner.target.config.size;
+        let format = self.format;
+        PendingRead::new(&self.runner.device, Some(receiver), move |buffer| {
+            TextureData {
+                buffer,
+                size,
+                format,
+            }
+        })
+    }
+
+    /// Reads the texture used as source by the post-process at `index`.
+    ///
+    /// The source of the first post-process is the target before any post-process is applied.
+    ///
+    /// # Panics
+    ///
+    /// This will panic if no post-process is registered at `index`.
+    pub fn read_post_process_source(&self, index: usize) -> TextureData {
+        TextureData {
+            buffer: self
+                .runner
+                .read_post_process_source(index)
+                .expect("no post-process registered at this index"),
+            size: self.runner.target.config.size,
+            format: self.format,
+        }
+    }
+
+    /// Reads an offscreen texture registered with [`App::with_offscreen_target`].
+    ///
+    /// # Panics
+    ///
+    /// This will panic if no offscreen target is registered with this name.
+    pub fn read_texture(&self, name: &str) -> TextureData {
+        let (index, target) = self
+            .app
+            .offscreen_targets
+            .iter()
+            .enumerate()
+            .find(|(_, target)| target.name == name)
+            .unwrap_or_else(|| panic!("no offscreen target `{name}` registered"));
+        let (buffer, format) = self.runner.read_offscreen_target(index);
+        TextureData {
+            buffer,
+            size: target.size,
+            format: TextureTargetFormat::from_wgpu(format),
+        }
+    }
+
+    /// Sets a fixed duration between two steps.
+    ///
+    /// This duration is used by [`delta_seconds`](crate::delta_seconds) and
+    /// [`elapsed_seconds`](crate::elapsed_seconds) instead of the real elapsed time, which makes
+    /// the application deterministic.
+    pub fn with_fixed_delta(mut self, delta: Duration) -> Self {
+        self.runner.time.fixed_delta = Some(delta);
+        self
+    }
+
+    /// Writes a value in a global variable stored on GPU side.
+    ///
+    /// The value is taken into account from the next step, including the first step. Global
+    /// variables are initialized with their default value before the value is written.
+    ///
+    /// If the passed global variable is not registered, nothing is written.
+    pub fn write<T: Gpu>(mut self, glob: T, value: &T::Cpu) -> Self {
+        self.runner.start();
+        let bytes = types::gpu_bytes(value);
+        self.runner.write(&glob.value(), &bytes);
+        self
+    }
+
+    /// Writes items in an array stored on GPU side, starting at index `start`.
+    ///
+    /// Only the bytes of the written items are sent to the GPU, which is faster than writing the
+    /// whole array with [`TextureApp::write`] for large arrays.
+    ///
+    /// The items are taken into account from the next step, including the first step.
+    ///
+    /// If the passed array is not a global variable, nothing is written.
+    ///
+    /// # Panics
+    ///
+    /// This will panic if the written items are out of the array bounds.
+    pub fn write_range<T: Gpu, const N: usize>(
+        mut self,
+        array: Array<T, N>,
+        start: usize,
+        items: &[T::Cpu],
+    ) -> Self {
+        self.runner.start();
+        write_array_range(&self.runner, array, start, items);
+        self
+    }
+
+    /// Sends an input event to the application.
+    ///
+    /// The event is taken into account from the next step, as if it had been received from a window.
+    pub fn inject_input(mut self, event: InputEvent) -> Self {
+        self.runner.input.handle(event);
+        self
+    }
+
     /// Runs the application during `update_count` steps.
     pub fn run(mut self, update_count: u64) -> Self {
         for _ in 0..update_count {
@@ -200,4 +884,97 @@ pub struct TextureData {
     pub buffer: Vec<u8>,
     /// Width and height of the texture.
     pub size: (u32, u32),
+    /// Format of the buffer.
+    pub format: TextureTargetFormat,
+}
+
+impl TextureData {
+    /// Converts the data to 8-bit RGBA components with sRGB encoding.
+    ///
+    /// Linear components are clamped between `0.0` and `1.0` and converted with sRGB encoding, so
+    /// that the result matches data read from a [`TextureTargetFormat::Rgba8UnormSrgb`] target.
+    /// Data already stored in this format are returned unchanged.
+    pub fn to_rgba8(&self) -> Self {
+        match self.format {
+            TextureTargetFormat::Rgba8UnormSrgb => Self {
+                buffer: self.buffer.clone(),
+                size: self.size,
+                format: self.format,
+            },
+            TextureTargetFormat::Rgba8Unorm => {
+                self.srgb_from_linear(self.buffer.iter().map(|&byte| f32::from(byte) / 255.))
+            }
+            // wgpu data are stored in little-endian
+            TextureTargetFormat::Rgba16Float => self.srgb_from_linear(
+                self.buffer
+                    .chunks_exact(2)
+                    .map(|bytes| f16_to_f32(u16::from_le_bytes([bytes[0], bytes[1]]))),
+            ),
+        }
+    }
+
+    fn srgb_from_linear(&self, components: impl Iterator<Item = f32>) -> Self {
+        Self {
+            buffer: components
+                .enumerate()
+                .map(|(index, component)| {
+                    let is_alpha = index % 4 == 3;
+                    unorm_to_u8(if is_alpha {
+                        component
+                    } else {
+                        linear_to_srgb(component)
+                    })
+                })
+                .collect(),
+            size: self.size,
+            format: TextureTargetFormat::Rgba8UnormSrgb,
+        }
+    }
+}
+
+fn f16_to_f32(bits: u16) -> f32 {
+    let sign = if bits & 0x8000 == 0 { 1. } else { -1. };
+    let exponent = i32::from((bits >> 10) & 0x1f);
+    let mantissa = f32::from(bits & 0x3ff);
+    match exponent {
+        0 => sign * mantissa * 2_f32.powi(-24),
+        0x1f if bits.trailing_zeros() >= 10 => sign * f32::INFINITY,
+        0x1f => f32::NAN,
+        _ => sign * (1. + mantissa / 1024.) * 2_f32.powi(exponent - 15),
+    }
+}
+
+fn linear_to_srgb(component: f32) -> f32 {
+    if component <= 0.003_130_8 {
+        component * 12.92
+    } else {
+        component.powf(1. / 2.4).mul_add(1.055, -0.055)
+    }
+}
+
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn unorm_to_u8(component: f32) -> u8 {
+    (component.clamp(0., 1.) * 255.).round() as u8
+}
+
+fn read_async<T: Gpu>(runner: &Runner, app: &App, value: T) -> PendingRead<Option<T::Cpu>> {
+    let receiver = runner.read_async(app, &value.value());
+    PendingRead::new(&runner.device, receiver, |bytes| {
+        snapshot::read_value::<T>(&bytes)
+    })
+}
+
+fn write_array_range<T: Gpu, const N: usize>(
+    runner: &Runner,
+    array: Array<T, N>,
+    start: usize,
+    items: &[T::Cpu],
+) {
+    assert!(
+        start.checked_add(items.len()).is_some_and(|end| end <= N),
+        "range {start}..{} out of bounds for array of length {N}",
+        start.saturating_add(items.len())
+    );
+    let offset = start as u64 * Array::<T, N>::item_stride();
+    runner.write_range(&array.value(), offset, &Array::<T, N>::items_to_gpu(items));
 }
@@ -0,0 +1,97 @@
+use crate::{wgsl, F32x2, Gpu};
+
+pub(crate) use gpu::CAMERA;
+pub use gpu::{Camera, CameraCpu};
+
+/// Returns the camera applied to shapes and sprites.
+///
+/// The returned camera can be modified by GPU code.
+///
+/// By default, the camera is configured so that world coordinates correspond to pixel coordinates
+/// of the target, with the origin at the top-left corner.
+pub fn camera() -> &'static Camera {
+    &CAMERA
+}
+
+/// Converts world coordinates to screen coordinates using the camera.
+///
+/// Screen coordinates are in pixels, with the origin at the top-left corner of the target.
+pub fn world_to_screen(position: F32x2) -> F32x2 {
+    crate::call_fn(wgsl::WORLD_TO_SCREEN_FN_NAME, vec![position.value()], false)
+}
+
+/// Converts screen coordinates to world coordinates using the camera.
+///
+/// Screen coordinates are in pixels, with the origin at the top-left corner of the target.
+pub fn screen_to_world(position: F32x2) -> F32x2 {
+    crate::call_fn(wgsl::SCREEN_TO_WORLD_FN_NAME, vec![position.value()], false)
+}
+
+/// Converts screen coordinates to normalized device coordinates.
+///
+/// Normalized device coordinates are between `-1.0` and `1.0`, with the origin at the center of
+/// the target and the Y-axis pointing up.
+pub fn screen_to_ndc(position: F32x2) -> F32x2 {
+    crate::call_fn(wgsl::SCREEN_TO_NDC_FN_NAME, vec![position.value()], false)
+}
+
+/// Converts normalized device coordinates to screen coordinates.
+///
+/// Normalized device coordinates are between `-1.0` and `1.0`, with the origin at the center of
+/// the target and the Y-axis pointing up.
+pub fn ndc_to_screen(position: F32x2) -> F32x2 {
+    crate::call_fn(wgsl::NDC_TO_SCREEN_FN_NAME, vec![position.value()], false)
+}
+
+/// Converts world coordinates to normalized device coordinates using the camera.
+///
+/// This can be used to compute the position returned by a `#[vertex]` function.
+pub fn world_to_ndc(position: F32x2) -> F32x2 {
+    crate::call_fn(wgsl::WORLD_TO_NDC_FN_NAME, vec![position.value()], false)
+}
+
+/// Converts normalized device coordinates to world coordinates using the camera.
+pub fn ndc_to_world(position: F32x2) -> F32x2 {
+    crate::call_fn(wgsl::NDC_TO_WORLD_FN_NAME, vec![position.value()], false)
+}
+
+/// Returns the mouse position in world coordinates.
+///
+/// See [`mouse_position`](crate::mouse_position) for the position in screen coordinates.
+pub fn mouse_world_position() -> F32x2 {
+    screen_to_world(crate::mouse_position())
+}
+
+#[crate::gpu]
+mod gpu {
+    use crate::{F32x2, F32};
+
+    pub(crate) static CAMERA: Camera = Camera {
+        position: crate::ndc_to_screen(F32x2::new(0., 0.)),
+        zoom: 1.,
+        rotation: 0.,
+        viewport_position: F32x2::new(0., 0.),
+        viewport_size: F32x2::new(1., 1.),
+    };
+
+    /// A 2D camera.
+    ///
+    /// The camera is retrieved with [`camera`](crate::camera()).
+    pub struct Camera {
+        /// The position in world coordinates displayed at the center of the viewport.
+        ///
+        /// By default, this is the center of the target when the application starts. The default
+        /// position is not updated when the target is resized.
+        pub position: F32x2,
+        /// The zoom factor, where `2.0` displays world items twice bigger.
+        pub zoom: F32,
+        /// The clockwise rotation in radians of the camera around its position.
+        pub rotation: F32,
+        /// The position of the top-left corner of the viewport, relative to the target size.
+        ///
+        /// Shapes and sprites are only displayed in the viewport.
+        pub viewport_position: F32x2,
+        /// The size of the viewport, relative to the target size.
+        pub viewport_size: F32x2,
+    }
+}
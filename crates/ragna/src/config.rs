@@ -0,0 +1,157 @@
+use wgpu::{
+    Backends, BlendComponent, BlendFactor, BlendOperation, BlendState, Features, Limits,
+    PowerPreference, TextureFormat,
+};
+
+/// The configuration used to select the GPU adapter and device.
+#[derive(Debug, Clone)]
+pub struct GpuConfig {
+    /// The allowed graphic backends.
+    ///
+    /// By default, the backends are retrieved from the `WGPU_BACKEND` environment variable if
+    /// defined, else all backends are allowed.
+    pub backends: Backends,
+    /// The power preference used to select the adapter.
+    pub power_preference: PowerPreference,
+    /// Whether only a fallback adapter (e.g. a software renderer) can be selected.
+    pub force_fallback_adapter: bool,
+    /// The limits the device must support.
+    pub required_limits: Limits,
+    /// The features the device must support.
+    pub required_features: Features,
+}
+
+impl Default for GpuConfig {
+    fn default() -> Self {
+        Self {
+            backends: Backends::from_env().unwrap_or_else(Backends::all),
+            power_preference: PowerPreference::default(),
+            force_fallback_adapter: false,
+            required_limits: Limits::default(),
+            required_features: Features::default(),
+        }
+    }
+}
+
+/// The configuration of a texture target.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TextureTargetConfig {
+    /// The format of the texture.
+    pub format: TextureTargetFormat,
+    /// The number of samples per pixel used for multisample anti-aliasing.
+    ///
+    /// `1` disables anti-aliasing. `4` is supported by all formats on all devices. Other values
+    /// are only supported if [`Features::TEXTURE_ADAPTER_SPECIFIC_FORMAT_FEATURES`] is part of
+    /// [`GpuConfig::required_features`] and the adapter supports them.
+    pub samples: u32,
+}
+
+impl Default for TextureTargetConfig {
+    fn default() -> Self {
+        Self {
+            format: TextureTargetFormat::default(),
+            samples: 1,
+        }
+    }
+}
+
+/// The format of a texture target.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[non_exhaustive]
+pub enum TextureTargetFormat {
+    /// RGBA components stored as 8-bit unsigned integers with sRGB encoding.
+    #[default]
+    Rgba8UnormSrgb,
+    /// RGBA components stored as 8-bit unsigned integers without encoding.
+    Rgba8Unorm,
+    /// RGBA components stored as 16-bit floats without encoding.
+    ///
+    /// This format supports high dynamic range, i.e. components outside `0.0..=1.0`.
+    Rgba16Float,
+}
+
+impl TextureTargetFormat {
+    /// Returns the number of bytes used to store a pixel.
+    pub fn pixel_size(self) -> usize {
+        match self {
+            Self::Rgba8UnormSrgb | Self::Rgba8Unorm => 4,
+            Self::Rgba16Float => 8,
+        }
+    }
+
+    pub(crate) fn to_wgpu(self) -> TextureFormat {
+        match self {
+            Self::Rgba8UnormSrgb => TextureFormat::Rgba8UnormSrgb,
+            Self::Rgba8Unorm => TextureFormat::Rgba8Unorm,
+            Self::Rgba16Float => TextureFormat::Rgba16Float,
+        }
+    }
+
+    pub(crate) fn from_wgpu(format: TextureFormat) -> Self {
+        [Self::Rgba8UnormSrgb, Self::Rgba8Unorm, Self::Rgba16Float]
+            .into_iter()
+            .find(|other_format| other_format.to_wgpu() == format)
+            .expect("internal error: unsupported texture format")
+    }
+}
+
+/// The configuration of a draw.
+///
+/// By default, draws are alpha blended and depth tested.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DrawConfig {
+    /// The way drawn colors are combined with the colors already in the target.
+    pub blend: BlendMode,
+    /// Whether drawn pixels behind the pixels already in the target are discarded.
+    pub depth_test: bool,
+    /// Whether the depth of drawn pixels is stored, so that pixels drawn later can be hidden.
+    ///
+    /// Translucent pixels also store their depth, so pixels drawn later behind them are discarded
+    /// instead of being blended. To blend overlapping translucent items, either draw them from
+    /// back to front, or disable this option.
+    pub depth_write: bool,
+}
+
+impl Default for DrawConfig {
+    fn default() -> Self {
+        Self {
+            blend: BlendMode::default(),
+            depth_test: true,
+            depth_write: true,
+        }
+    }
+}
+
+/// The way drawn colors are combined with the colors already in the target.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[non_exhaustive]
+pub enum BlendMode {
+    /// The drawn color is mixed with the target color depending on the drawn alpha component.
+    #[default]
+    Alpha,
+    /// The drawn color multiplied by its alpha component is added to the target color.
+    Additive,
+    /// The drawn color replaces the target color, including the alpha component.
+    Replace,
+}
+
+impl BlendMode {
+    pub(crate) fn to_wgpu(self) -> BlendState {
+        match self {
+            Self::Alpha => BlendState::ALPHA_BLENDING,
+            Self::Additive => BlendState {
+                color: BlendComponent {
+                    src_factor: BlendFactor::SrcAlpha,
+                    dst_factor: BlendFactor::One,
+                    operation: BlendOperation::Add,
+                },
+                alpha: BlendComponent {
+                    src_factor: BlendFactor::One,
+                    dst_factor: BlendFactor::One,
+                    operation: BlendOperation::Add,
+                },
+            },
+            Self::Replace => BlendState::REPLACE,
+        }
+    }
+}
@@ -1,27 +1,17 @@
-use crate::app::CURRENT_CTX;
 use crate::operations::Operation;
+use crate::types::GpuValuePaths;
 use crate::{Gpu, GpuTypeDetails};
-use once_cell::sync::{Lazy, OnceCell};
 use std::any::Any;
+use std::cell::RefCell;
 use std::mem;
-use std::sync::atomic::{AtomicU32, AtomicUsize, Ordering};
-use std::sync::{LockResult, Mutex, MutexGuard};
 
-pub(crate) fn next_var_id() -> u32 {
-    static NEXT_ID: AtomicU32 = AtomicU32::new(0);
-    NEXT_ID.fetch_add(1, Ordering::Relaxed)
+thread_local! {
+    // stack of contexts to support nested tracing on the same thread
+    static CURRENT_CTXS: RefCell<Vec<GpuContext>> = const { RefCell::new(vec![]) };
 }
 
 pub(crate) fn next_static_value<'a, T: Gpu>(value: T) -> &'a T {
-    static VALUES: Lazy<Vec<OnceCell<Box<dyn Any + Sync + Send>>>> =
-        Lazy::new(|| (0..10_000).map(|_| OnceCell::new() as _).collect());
-    static NEXT_INDEX: AtomicUsize = AtomicUsize::new(0);
-    VALUES
-        .get(NEXT_INDEX.fetch_add(1, Ordering::Relaxed))
-        .expect("index call limit reached")
-        .get_or_init(|| Box::new(value) as _)
-        .downcast_ref()
-        .expect("internal error: invalid static value type")
+    GpuContext::run_current(|ctx| ctx.store_value(value))
 }
 
 /// The context used to track GPU operations.
@@ -30,6 +20,10 @@ pub(crate) fn next_static_value<'a, T: Gpu>(value: T) -> &'a T {
 pub struct GpuContext {
     pub(crate) operations: Vec<Operation>,
     pub(crate) types: Vec<GpuTypeDetails>,
+    pub(crate) paths: GpuValuePaths,
+    next_var_id: u32,
+    // values referenced during tracing, dropped with the context
+    values: Vec<Box<dyn Any + Sync + Send>>,
 }
 
 impl GpuContext {
@@ -44,33 +38,65 @@ impl GpuContext {
         }
     }
 
+    pub(crate) fn next_var_id(&mut self) -> u32 {
+        let id = self.next_var_id;
+        self.next_var_id += 1;
+        id
+    }
+
+    #[allow(unsafe_code)]
+    fn store_value<'a, T: Gpu>(&mut self, value: T) -> &'a T {
+        self.values.push(Box::new(value));
+        let value: *const T = self
+            .values
+            .last()
+            .and_then(|value| value.downcast_ref())
+            .expect("internal error: invalid stored value type");
+        // SAFETY: values are boxed, so their address is stable when `values` grows, and they are
+        // only dropped with the context, i.e. with the app that owns the traced operations.
+        unsafe { &*value }
+    }
+
     pub(crate) fn run_current<O>(f: impl FnOnce(&mut Self) -> O) -> O {
-        f(CURRENT_CTX
-            .try_lock()
-            .as_mut()
-            .expect("cannot lock GPU context")
-            .as_mut()
-            .expect("internal error: missing GPU context"))
+        CURRENT_CTXS.with(|ctxs| {
+            f(ctxs
+                .try_borrow_mut()
+                .expect("cannot lock GPU context")
+                .last_mut()
+                .expect("internal error: missing GPU context"))
+        })
     }
 
-    pub(crate) fn lock_current<'a>() -> LockResult<MutexGuard<'a, ()>> {
-        static CTX_LOCK: Mutex<()> = Mutex::new(());
-        let lock = CTX_LOCK.lock();
-        **CURRENT_CTX
-            .try_lock()
-            .as_mut()
-            .expect("cannot lock GPU context") = Some(Self::default());
-        lock
+    pub(crate) fn record(f: impl FnOnce()) -> Self {
+        let guard = RecordGuard::new();
+        f();
+        guard.finish()
     }
+}
+
+// removes the recorded context from the stack even if the tracing panics
+struct RecordGuard;
 
-    pub(crate) fn unlock_current(lock: LockResult<MutexGuard<'_, ()>>) -> Self {
-        let ctx = CURRENT_CTX
-            .try_lock()
-            .as_mut()
-            .expect("cannot lock GPU context")
-            .take()
-            .expect("internal error: missing GPU context");
-        drop(lock);
+impl RecordGuard {
+    fn new() -> Self {
+        CURRENT_CTXS.with_borrow_mut(|ctxs| ctxs.push(GpuContext::default()));
+        Self
+    }
+
+    fn finish(self) -> GpuContext {
+        let ctx = CURRENT_CTXS.with_borrow_mut(|ctxs| {
+            mem::take(
+                ctxs.last_mut()
+                    .expect("internal error: missing GPU context"),
+            )
+        });
+        drop(self);
         ctx
     }
 }
+
+impl Drop for RecordGuard {
+    fn drop(&mut self) {
+        CURRENT_CTXS.with_borrow_mut(Vec::pop);
+    }
+}
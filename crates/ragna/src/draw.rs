@@ -0,0 +1,136 @@
+use crate::context::GpuContext;
+use crate::operations::{ConstantAssignVarOperation, ConstantValue, Operation};
+use crate::{wgsl, DrawConfig, F32x4, Gpu, GpuTypeDetails, GpuValue, Wgsl, WgslConstructor, U32};
+use fxhash::FxHashMap;
+use std::any::TypeId;
+use wgpu::PrimitiveTopology;
+
+#[derive(Debug)]
+#[allow(clippy::large_enum_variant)]
+pub(crate) enum Draw {
+    Pixels,
+    Shapes { shapes: GpuValue, count: u32 },
+    Sprites { sprites: GpuValue, count: u32 },
+    Text { text: GpuValue, capacity: u32 },
+    Custom(CustomDraw),
+    PostProcess(CustomDraw),
+}
+
+impl Draw {
+    pub(crate) fn vertex_count(&self) -> u32 {
+        match self {
+            Self::Pixels | Self::Shapes { .. } | Self::Sprites { .. } | Self::Text { .. } => 4,
+            Self::Custom(draw) | Self::PostProcess(draw) => draw.vertex_count,
+        }
+    }
+
+    pub(crate) fn instance_count(&self) -> u32 {
+        match self {
+            Self::Pixels => 1,
+            Self::Shapes { count, .. } | Self::Sprites { count, .. } => *count,
+            Self::Text { capacity, .. } => *capacity,
+            Self::Custom(draw) | Self::PostProcess(draw) => draw.instance_count,
+        }
+    }
+
+    pub(crate) fn topology(&self) -> PrimitiveTopology {
+        match self {
+            Self::Pixels | Self::Shapes { .. } | Self::Sprites { .. } | Self::Text { .. } => {
+                PrimitiveTopology::TriangleStrip
+            }
+            Self::Custom(_) | Self::PostProcess(_) => PrimitiveTopology::TriangleList,
+        }
+    }
+
+    pub(crate) fn wgsl_code(
+        &self,
+        types: &FxHashMap<TypeId, (usize, GpuTypeDetails)>,
+        globs: &[GpuValue],
+    ) -> String {
+        match self {
+            Self::Pixels => wgsl::PIXEL_SHADER.into(),
+            Self::Shapes { shapes, .. } => wgsl::shape_shader_code(shapes, globs),
+            Self::Sprites { sprites, .. } => wgsl::sprite_shader_code(sprites, globs),
+            Self::Text { text, .. } => wgsl::text_shader_code(text, globs),
+            Self::Custom(draw) => wgsl::render_shader_code(draw, types, globs),
+            Self::PostProcess(draw) => wgsl::post_process_shader_code(draw, types, globs),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct OffscreenTarget {
+    pub(crate) name: String,
+    pub(crate) texture_index: usize,
+    pub(crate) size: (u32, u32),
+    pub(crate) draws: Vec<(Draw, DrawConfig)>,
+}
+
+#[derive(Debug)]
+pub(crate) struct CustomDraw {
+    pub(crate) vertex_ctx: GpuContext,
+    pub(crate) vertex_output: GpuValue,
+    pub(crate) fragment_ctx: GpuContext,
+    pub(crate) fragment_output: GpuValue,
+    vertex_count: u32,
+    instance_count: u32,
+}
+
+impl CustomDraw {
+    pub(crate) fn new<T: Gpu>(
+        vertex: impl FnOnce(U32, U32) -> T,
+        fragment: impl FnOnce(T) -> F32x4,
+        vertex_count: u32,
+        instance_count: u32,
+    ) -> Self {
+        let mut vertex_output = None;
+        let vertex_ctx = GpuContext::record(|| {
+            let vertex_index = input_var(Wgsl::Value("vertex_index".into()));
+            let instance_index = input_var(Wgsl::Value("instance_index".into()));
+            let output = crate::create_var(vertex(vertex_index, instance_index));
+            vertex_output = Some(output.value());
+        });
+        let mut fragment_output = None;
+        let fragment_ctx = GpuContext::record(|| {
+            let input = input_var(fragment_input_wgsl::<T>());
+            let output = crate::create_var(fragment(input));
+            fragment_output = Some(output.value());
+        });
+        Self {
+            vertex_ctx,
+            vertex_output: vertex_output.expect("internal error: missing vertex output"),
+            fragment_ctx,
+            fragment_output: fragment_output.expect("internal error: missing fragment output"),
+            vertex_count,
+            instance_count,
+        }
+    }
+}
+
+fn input_var<T: Gpu>(value: Wgsl) -> T {
+    let var = crate::create_uninit_var::<T>();
+    GpuContext::run_current(|ctx| {
+        ctx.operations
+            .push(Operation::ConstantAssignVar(ConstantAssignVarOperation {
+                left_value: var.value(),
+                right_value: ConstantValue::Wgsl(value),
+            }));
+    });
+    var
+}
+
+fn fragment_input_wgsl<T: Gpu>() -> Wgsl {
+    let details = T::details();
+    if details.is_struct() {
+        Wgsl::Constructor(WgslConstructor {
+            type_id: details.type_id,
+            args: (0..details.field_types.len())
+                .map(|index| {
+                    Wgsl::Value(format!("{}.{}", wgsl::VERTEX_VAR, wgsl::field_name(index)))
+                })
+                .collect(),
+        })
+    } else {
+        Wgsl::Value(format!("{}.{}", wgsl::VERTEX_VAR, wgsl::field_name(0)))
+    }
+}
@@ -0,0 +1,85 @@
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+
+/// An error that occurs when initializing a Ragna application.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum RagnaError {
+    /// No supported graphic adapter has been found.
+    AdapterNotFound,
+    /// The graphic device cannot be retrieved from the adapter.
+    DeviceRequestFailed {
+        /// The error message.
+        message: String,
+    },
+    /// A generated shader is invalid.
+    ShaderValidation {
+        /// The error message.
+        message: String,
+        /// The generated WGSL code around the error location.
+        ///
+        /// If the location is unknown, the whole shader code is returned.
+        wgsl_excerpt: String,
+    },
+    /// The buffer containing the global variables is too large for the device.
+    BufferTooLarge {
+        /// The buffer size in bytes.
+        size: u64,
+        /// The maximum buffer size in bytes supported by the device.
+        max_size: u64,
+    },
+    /// The number of textures is too large for the device.
+    TooManyTextures {
+        /// The number of textures sampled by a shader, including the source texture of
+        /// post-processes.
+        count: u32,
+        /// The maximum number of textures per shader supported by the device.
+        max_count: u32,
+    },
+    /// An image cannot be loaded.
+    ImageLoading {
+        /// The path of the image file.
+        path: String,
+        /// The error message.
+        message: String,
+    },
+    /// The number of samples per pixel of the target is not supported by the device.
+    UnsupportedSampleCount {
+        /// The number of samples per pixel.
+        samples: u32,
+    },
+}
+
+impl Display for RagnaError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::AdapterNotFound => write!(f, "no supported graphic adapter found"),
+            Self::DeviceRequestFailed { message } => {
+                write!(f, "error when retrieving graphic device: {message}")
+            }
+            Self::ShaderValidation {
+                message,
+                wgsl_excerpt,
+            } => write!(f, "invalid generated shader: {message}\n\n{wgsl_excerpt}"),
+            Self::BufferTooLarge { size, max_size } => write!(
+                f,
+                "buffer of globals is too large ({size} bytes, {max_size} bytes maximum)"
+            ),
+            Self::TooManyTextures { count, max_count } => write!(
+                f,
+                "too many textures sampled by a shader ({count} textures, {max_count} textures maximum)"
+            ),
+            Self::ImageLoading { path, message } => {
+                write!(f, "cannot load image `{path}`: {message}")
+            }
+            Self::UnsupportedSampleCount { samples } => {
+                write!(
+                    f,
+                    "{samples} samples per pixel not supported for the target"
+                )
+            }
+        }
+    }
+}
+
+impl Error for RagnaError {}
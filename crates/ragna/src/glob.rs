@@ -25,3 +25,16 @@ impl<T> Deref for Glob<T> {
         &self.inner
     }
 }
+
+// The default value of a built-in glob is the glob itself, as the runner writes the value
+// before each step.
+macro_rules! builtin_globs {
+    ($($vis:vis static $name:ident: $type_:ty = $id:literal;)+) => {
+        $($vis static $name: $crate::Glob<$type_> = $crate::Glob::new(
+            || $crate::create_glob(&$id),
+            || $crate::create_glob(&$id),
+        );)+
+    };
+}
+
+pub(crate) use builtin_globs;
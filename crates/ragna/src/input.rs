@@ -0,0 +1,235 @@
+use crate::glob::builtin_globs;
+use crate::types::gpu_bytes;
+use crate::{f32x2, Array, Bool, Cpu, F32x2, Gpu, GpuValue};
+use winit::event::MouseButton;
+use winit::keyboard::KeyCode;
+
+const KEY_COUNT: usize = 256;
+const MOUSE_BUTTON_COUNT: usize = 8;
+
+builtin_globs! {
+    pub(crate) static PRESSED_KEYS: Array<Bool, KEY_COUNT> = "ragna::pressed_keys";
+    pub(crate) static JUST_PRESSED_KEYS: Array<Bool, KEY_COUNT> = "ragna::just_pressed_keys";
+    pub(crate) static JUST_RELEASED_KEYS: Array<Bool, KEY_COUNT> = "ragna::just_released_keys";
+    pub(crate) static PRESSED_MOUSE_BUTTONS: Array<Bool, MOUSE_BUTTON_COUNT> =
+        "ragna::pressed_mouse_buttons";
+    pub(crate) static JUST_PRESSED_MOUSE_BUTTONS: Array<Bool, MOUSE_BUTTON_COUNT> =
+        "ragna::just_pressed_mouse_buttons";
+    pub(crate) static JUST_RELEASED_MOUSE_BUTTONS: Array<Bool, MOUSE_BUTTON_COUNT> =
+        "ragna::just_released_mouse_buttons";
+    pub(crate) static MOUSE_POSITION: F32x2 = "ragna::mouse_position";
+    pub(crate) static MOUSE_WHEEL_DELTA: F32x2 = "ragna::mouse_wheel_delta";
+}
+
+/// Returns whether a keyboard key is currently pressed.
+pub fn is_key_pressed(key: KeyCode) -> Bool {
+    state_item(*PRESSED_KEYS, key as usize)
+}
+
+/// Returns whether a keyboard key has been pressed since the previous step.
+pub fn is_key_just_pressed(key: KeyCode) -> Bool {
+    state_item(*JUST_PRESSED_KEYS, key as usize)
+}
+
+/// Returns whether a keyboard key has been released since the previous step.
+pub fn is_key_just_released(key: KeyCode) -> Bool {
+    state_item(*JUST_RELEASED_KEYS, key as usize)
+}
+
+/// Returns whether a mouse button is currently pressed.
+pub fn is_mouse_button_pressed(button: MouseButton) -> Bool {
+    state_item(*PRESSED_MOUSE_BUTTONS, mouse_button_position(button))
+}
+
+/// Returns whether a mouse button has been pressed since the previous step.
+pub fn is_mouse_button_just_pressed(button: MouseButton) -> Bool {
+    state_item(*JUST_PRESSED_MOUSE_BUTTONS, mouse_button_position(button))
+}
+
+/// Returns whether a mouse button has been released since the previous step.
+pub fn is_mouse_button_just_released(button: MouseButton) -> Bool {
+    state_item(*JUST_RELEASED_MOUSE_BUTTONS, mouse_button_position(button))
+}
+
+/// Returns the last known position of the mouse cursor in pixels.
+///
+/// The origin is the top-left corner of the target.
+pub fn mouse_position() -> F32x2 {
+    crate::create_var(*MOUSE_POSITION)
+}
+
+/// Returns the scroll amount of the mouse wheel since the previous step.
+///
+/// The amount is expressed in lines if the device reports lines, else in pixels.
+pub fn mouse_wheel_delta() -> F32x2 {
+    crate::create_var(*MOUSE_WHEEL_DELTA)
+}
+
+/// An input event that can be sent to an application.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[non_exhaustive]
+pub enum InputEvent {
+    /// A keyboard key has been pressed.
+    KeyPressed(KeyCode),
+    /// A keyboard key has been released.
+    KeyReleased(KeyCode),
+    /// A mouse button has been pressed.
+    MouseButtonPressed(MouseButton),
+    /// A mouse button has been released.
+    MouseButtonReleased(MouseButton),
+    /// The mouse cursor has moved to a new position in pixels.
+    MouseMoved {
+        /// The horizontal position from the left of the target.
+        x: f32,
+        /// The vertical position from the top of the target.
+        y: f32,
+    },
+    /// The mouse wheel has been scrolled.
+    MouseWheelScrolled {
+        /// The horizontal scroll amount.
+        x: f32,
+        /// The vertical scroll amount.
+        y: f32,
+    },
+}
+
+#[derive(Debug)]
+pub(crate) struct InputState {
+    keys: ButtonStates<KEY_COUNT>,
+    mouse_buttons: ButtonStates<MOUSE_BUTTON_COUNT>,
+    mouse_position: (f32, f32),
+    mouse_wheel_delta: (f32, f32),
+}
+
+impl Default for InputState {
+    fn default() -> Self {
+        Self {
+            keys: ButtonStates::default(),
+            mouse_buttons: ButtonStates::default(),
+            mouse_position: (0., 0.),
+            mouse_wheel_delta: (0., 0.),
+        }
+    }
+}
+
+impl InputState {
+    pub(crate) fn handle(&mut self, event: InputEvent) {
+        match event {
+            InputEvent::KeyPressed(key) => self.keys.press(key as usize),
+            InputEvent::KeyReleased(key) => self.keys.release(key as usize),
+            InputEvent::MouseButtonPressed(button) => {
+                self.mouse_buttons.press(mouse_button_position(button));
+            }
+            InputEvent::MouseButtonReleased(button) => {
+                self.mouse_buttons.release(mouse_button_position(button));
+            }
+            InputEvent::MouseMoved { x, y } => self.mouse_position = (x, y),
+            InputEvent::MouseWheelScrolled { x, y } => {
+                self.mouse_wheel_delta.0 += x;
+                self.mouse_wheel_delta.1 += y;
+            }
+        }
+    }
+
+    pub(crate) fn glob_bytes(&self) -> [(GpuValue, Vec<u8>); 8] {
+        [
+            (PRESSED_KEYS.value(), gpu_bytes(&self.keys.pressed)),
+            (
+                JUST_PRESSED_KEYS.value(),
+                gpu_bytes(&self.keys.just_pressed),
+            ),
+            (
+                JUST_RELEASED_KEYS.value(),
+                gpu_bytes(&self.keys.just_released),
+            ),
+            (
+                PRESSED_MOUSE_BUTTONS.value(),
+                gpu_bytes(&self.mouse_buttons.pressed),
+            ),
+            (
+                JUST_PRESSED_MOUSE_BUTTONS.value(),
+                gpu_bytes(&self.mouse_buttons.just_pressed),
+            ),
+            (
+                JUST_RELEASED_MOUSE_BUTTONS.value(),
+                gpu_bytes(&self.mouse_buttons.just_released),
+            ),
+            (MOUSE_POSITION.value(), f32x2_bytes(self.mouse_position)),
+            (
+                MOUSE_WHEEL_DELTA.value(),
+                f32x2_bytes(self.mouse_wheel_delta),
+            ),
+        ]
+    }
+
+    pub(crate) fn end_step(&mut self) {
+        self.keys.end_step();
+        self.mouse_buttons.end_step();
+        self.mouse_wheel_delta = (0., 0.);
+    }
+}
+
+#[derive(Debug)]
+struct ButtonStates<const N: usize> {
+    pressed: [bool; N],
+    just_pressed: [bool; N],
+    just_released: [bool; N],
+}
+
+impl<const N: usize> Default for ButtonStates<N> {
+    fn default() -> Self {
+        Self {
+            pressed: [false; N],
+            just_pressed: [false; N],
+            just_released: [false; N],
+        }
+    }
+}
+
+impl<const N: usize> ButtonStates<N> {
+    fn press(&mut self, position: usize) {
+        if position < N {
+            self.just_pressed[position] |= !self.pressed[position];
+            self.pressed[position] = true;
+        }
+    }
+
+    fn release(&mut self, position: usize) {
+        if position < N {
+            self.just_released[position] |= self.pressed[position];
+            self.pressed[position] = false;
+        }
+    }
+
+    fn end_step(&mut self) {
+        self.just_pressed = [false; N];
+        self.just_released = [false; N];
+    }
+}
+
+fn state_item<const N: usize>(states: Array<Bool, N>, position: usize) -> Bool {
+    if position < N {
+        #[allow(clippy::cast_possible_truncation)]
+        crate::create_var(states[(position as u32).to_gpu()])
+    } else {
+        false.to_gpu()
+    }
+}
+
+fn mouse_button_position(button: MouseButton) -> usize {
+    match button {
+        MouseButton::Left => 0,
+        MouseButton::Right => 1,
+        MouseButton::Middle => 2,
+        MouseButton::Back => 3,
+        MouseButton::Forward => 4,
+        MouseButton::Other(id) => usize::from(id) + 5,
+    }
+}
+
+fn f32x2_bytes(value: (f32, f32)) -> Vec<u8> {
+    gpu_bytes(&f32x2 {
+        x: value.0,
+        y: value.1,
+    })
+}
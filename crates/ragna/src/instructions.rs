@@ -2,7 +2,7 @@ use crate::context::GpuContext;
 use crate::operations::{
     AssignVarOperation, DeclareVarOperation, FnCallOperation, IfOperation, Operation,
 };
-use crate::{context, Bool, Gpu, GpuValue};
+use crate::{Bool, Gpu, GpuValue};
 
 #[doc(hidden)]
 #[allow(clippy::trivially_copy_pass_by_ref)]
@@ -14,7 +14,7 @@ pub fn create_glob<T: Gpu>(id: &'static &'static str) -> T {
 pub fn create_uninit_var<T: Gpu>() -> T {
     let id = GpuContext::run_current(|ctx| {
         ctx.register_type::<T>();
-        let id = context::next_var_id();
+        let id = ctx.next_var_id();
         ctx.operations
             .push(Operation::DeclareVar(DeclareVarOperation {
                 id,
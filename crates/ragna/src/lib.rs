@@ -1,27 +1,64 @@
 //! A library for creating easily a GPU-native application.
 
+extern crate self as ragna;
+
 mod app;
+mod camera;
+mod config;
 mod context;
+mod draw;
+mod error;
 mod glob;
+mod input;
 mod instructions;
+mod offscreen;
 mod operations;
 mod operators;
+mod pending;
+mod post_process;
 mod runner;
+mod shape;
+mod snapshot;
+mod sprite;
+mod stats;
+mod target;
 mod testing;
+mod text;
+mod texture;
+mod time;
 mod types;
 mod wgsl;
 
 pub use app::*;
+pub use camera::*;
+pub use config::*;
 pub use context::*;
+pub use error::*;
 pub use glob::*;
+pub use input::*;
 pub use instructions::*;
+pub use offscreen::*;
 pub use operators::*;
+pub use pending::*;
+pub use post_process::*;
+pub use shape::*;
+pub use snapshot::*;
+pub use sprite::*;
+pub use stats::*;
+pub use target::*;
 pub use testing::*;
+pub use text::*;
+pub use texture::*;
+pub use time::*;
 pub use types::array::*;
 pub use types::primitive::*;
 pub use types::range::*;
 pub use types::vectors::*;
 pub use types::*;
 
+pub use wgpu::{Backends, Features, Limits, PowerPreference};
+pub use winit::event::MouseButton;
+pub use winit::keyboard::KeyCode;
+
 /// Transforms a Rust module to a GPU module.
 pub use ragna_derive::gpu;
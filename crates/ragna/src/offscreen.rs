@@ -0,0 +1,110 @@
+use crate::{App, Array, DrawConfig, F32x4, Glob, Gpu, Shape, Sprite, Text, U32};
+
+/// The draws rendered in an offscreen target.
+///
+/// Draws are registered with [`App::with_offscreen_target`].
+#[derive(Debug)]
+pub struct OffscreenDraws {
+    // the draws of the main target are moved out of the application during the registration
+    pub(crate) app: App,
+}
+
+impl OffscreenDraws {
+    /// Draws at each step the shapes stored in a global variable.
+    ///
+    /// See [`App::with_shapes`] for more details.
+    pub fn with_shapes<const N: usize>(self, shapes: &Glob<Array<Shape, N>>) -> Self {
+        self.with_shapes_config(shapes, DrawConfig::default())
+    }
+
+    /// Draws at each step the shapes stored in a global variable using a specific configuration.
+    ///
+    /// See [`App::with_shapes`] for more details.
+    pub fn with_shapes_config<const N: usize>(
+        self,
+        shapes: &Glob<Array<Shape, N>>,
+        config: DrawConfig,
+    ) -> Self {
+        Self {
+            app: self.app.with_shapes_config(shapes, config),
+        }
+    }
+
+    /// Draws at each step the sprites stored in a global variable.
+    ///
+    /// See [`App::with_sprites`] for more details.
+    pub fn with_sprites<const N: usize>(self, sprites: &Glob<Array<Sprite, N>>) -> Self {
+        self.with_sprites_config(sprites, DrawConfig::default())
+    }
+
+    /// Draws at each step the sprites stored in a global variable using a specific configuration.
+    ///
+    /// See [`App::with_sprites`] for more details.
+    pub fn with_sprites_config<const N: usize>(
+        self,
+        sprites: &Glob<Array<Sprite, N>>,
+        config: DrawConfig,
+    ) -> Self {
+        Self {
+            app: self.app.with_sprites_config(sprites, config),
+        }
+    }
+
+    /// Draws at each step the text stored in a global variable.
+    ///
+    /// See [`App::with_text`] for more details.
+    pub fn with_text<const N: usize>(self, text: &Glob<Text<N>>) -> Self {
+        self.with_text_config(text, DrawConfig::default())
+    }
+
+    /// Draws at each step the text stored in a global variable using a specific configuration.
+    ///
+    /// See [`App::with_text`] for more details.
+    pub fn with_text_config<const N: usize>(
+        self,
+        text: &Glob<Text<N>>,
+        config: DrawConfig,
+    ) -> Self {
+        Self {
+            app: self.app.with_text_config(text, config),
+        }
+    }
+
+    /// Draws at each step primitives defined by a vertex function and a fragment function.
+    ///
+    /// See [`App::with_draw`] for more details.
+    pub fn with_draw<T: Gpu>(
+        self,
+        vertex: impl FnOnce(U32, U32) -> T,
+        fragment: impl FnOnce(T) -> F32x4,
+        vertex_count: u32,
+        instance_count: u32,
+    ) -> Self {
+        self.with_draw_config(
+            vertex,
+            fragment,
+            vertex_count,
+            instance_count,
+            DrawConfig::default(),
+        )
+    }
+
+    /// Draws at each step primitives defined by a vertex function and a fragment function using a
+    /// specific configuration.
+    ///
+    /// See [`App::with_draw`] for more details.
+    pub fn with_draw_config<T: Gpu>(
+        self,
+        vertex: impl FnOnce(U32, U32) -> T,
+        fragment: impl FnOnce(T) -> F32x4,
+        vertex_count: u32,
+        instance_count: u32,
+        config: DrawConfig,
+    ) -> Self {
+        Self {
+            app: self
+                .app
+                .with_draw_config(vertex, fragment, vertex_count, instance_count, config),
+        }
+    }
+}
@@ -1,5 +1,9 @@
 use crate::types::GpuTypeDetails;
 use crate::{GpuValue, Wgsl};
+use fxhash::FxHashMap;
+use std::any::TypeId;
+
+pub(crate) const ARRAY_FN_NAME: &str = "array";
 
 #[derive(Debug)]
 #[allow(clippy::large_enum_variant)]
@@ -11,6 +15,7 @@ pub(crate) enum Operation {
     Binary(BinaryOperation),
     FnCall(FnCallOperation),
     IfBlock(IfOperation),
+    SetPixel(SetPixelOperation),
     ElseBlock,
     LoopBlock,
     EndBlock,
@@ -18,6 +23,47 @@ pub(crate) enum Operation {
     Continue,
 }
 
+// returns the bytes of `value` if `operations` only build it from values known on CPU side
+pub(crate) fn constant_bytes(
+    operations: &[Operation],
+    value: &GpuValue,
+    types: &FxHashMap<TypeId, (usize, GpuTypeDetails)>,
+) -> Option<Vec<u8>> {
+    let mut constants = FxHashMap::default();
+    for operation in operations {
+        match operation {
+            Operation::DeclareVar(_) => {}
+            Operation::ConstantAssignVar(op) => {
+                constants.insert(op.left_value, op.right_value.bytes()?.to_vec());
+            }
+            Operation::FnCall(op) if op.fn_name == ARRAY_FN_NAME => {
+                let mut bytes =
+                    vec![0; usize::try_from(types.get(&op.var.type_id)?.1.size()).ok()?];
+                let stride = bytes.len().checked_div(op.args.len())?;
+                for (arg, item_bytes) in op.args.iter().zip(bytes.chunks_mut(stride)) {
+                    let arg_bytes = constants.get(arg)?;
+                    item_bytes
+                        .get_mut(..arg_bytes.len())?
+                        .copy_from_slice(arg_bytes);
+                }
+                constants.insert(op.var, bytes);
+            }
+            Operation::AssignVar(_)
+            | Operation::Unary(_)
+            | Operation::Binary(_)
+            | Operation::FnCall(_)
+            | Operation::IfBlock(_)
+            | Operation::SetPixel(_)
+            | Operation::ElseBlock
+            | Operation::LoopBlock
+            | Operation::EndBlock
+            | Operation::Break
+            | Operation::Continue => return None,
+        }
+    }
+    constants.remove(value)
+}
+
 #[derive(Debug)]
 pub(crate) struct DeclareVarOperation {
     pub(crate) id: u32,
@@ -33,7 +79,33 @@ pub(crate) struct AssignVarOperation {
 #[derive(Debug)]
 pub(crate) struct ConstantAssignVarOperation {
     pub(crate) left_value: GpuValue,
-    pub(crate) right_value: Wgsl,
+    pub(crate) right_value: ConstantValue,
+}
+
+#[derive(Debug)]
+pub(crate) enum ConstantValue {
+    Wgsl(Wgsl),
+    // the WGSL code is only generated if the value is embedded in a shader
+    Cpu {
+        bytes: Vec<u8>,
+        to_wgsl: fn(&[u8]) -> Wgsl,
+    },
+}
+
+impl ConstantValue {
+    pub(crate) fn to_wgsl(&self) -> Wgsl {
+        match self {
+            Self::Wgsl(wgsl) => wgsl.clone(),
+            Self::Cpu { bytes, to_wgsl } => to_wgsl(bytes),
+        }
+    }
+
+    pub(crate) fn bytes(&self) -> Option<&[u8]> {
+        match self {
+            Self::Wgsl(_) => None,
+            Self::Cpu { bytes, .. } => Some(bytes),
+        }
+    }
 }
 
 #[derive(Debug)]
@@ -64,3 +136,9 @@ pub(crate) struct FnCallOperation {
 pub(crate) struct IfOperation {
     pub(crate) condition: GpuValue,
 }
+
+#[derive(Debug)]
+pub(crate) struct SetPixelOperation {
+    pub(crate) position: GpuValue,
+    pub(crate) color: GpuValue,
+}
@@ -0,0 +1,96 @@
+use futures::channel::oneshot;
+use std::fmt::{Debug, Formatter};
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use wgpu::Device;
+
+/// A read of GPU data that completes without blocking the calling thread.
+///
+/// The result can be retrieved either by awaiting the read, or by calling
+/// [`PendingRead::try_read`] regularly, e.g. once per step in a window application.
+///
+/// When the read is awaited on a native platform, the device is polled each time the task is
+/// polled, and the task asks to be polled again until the data are retrieved.
+/// On the web, the task is woken by the browser once the data are retrieved.
+pub struct PendingRead<T> {
+    device: Device,
+    // `None` if there is no data to retrieve from the GPU
+    receiver: Option<oneshot::Receiver<Vec<u8>>>,
+    convert: Option<Box<dyn FnOnce(Vec<u8>) -> T + Send>>,
+}
+
+impl<T> Debug for PendingRead<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PendingRead")
+            .field("receiver", &self.receiver)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<T> PendingRead<T> {
+    pub(crate) fn new(
+        device: &Device,
+        receiver: Option<oneshot::Receiver<Vec<u8>>>,
+        convert: impl FnOnce(Vec<u8>) -> T + Send + 'static,
+    ) -> Self {
+        Self {
+            device: device.clone(),
+            receiver,
+            convert: Some(Box::new(convert)),
+        }
+    }
+
+    /// Returns the result of the read if the GPU has retrieved the data.
+    ///
+    /// This method never blocks. It polls the device once, so that the data are made available
+    /// as soon as the GPU has retrieved them.
+    ///
+    /// # Panics
+    ///
+    /// This will panic if the result has already been returned, or if the GPU has failed to
+    /// retrieve the data.
+    pub fn try_read(&mut self) -> Poll<T> {
+        let bytes = if let Some(receiver) = &mut self.receiver {
+            // retrieval callbacks are only run when the device is polled on native platforms
+            self.device.poll(wgpu::Maintain::Poll);
+            match receiver.try_recv() {
+                Ok(Some(bytes)) => bytes,
+                Ok(None) => return Poll::Pending,
+                Err(oneshot::Canceled) => panic!("GPU data retrieval failed"),
+            }
+        } else {
+            vec![]
+        };
+        Poll::Ready(self.complete(bytes))
+    }
+
+    fn complete(&mut self, bytes: Vec<u8>) -> T {
+        let convert = self.convert.take().expect("read already completed");
+        convert(bytes)
+    }
+}
+
+impl<T> Future for PendingRead<T> {
+    type Output = T;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        let bytes = if let Some(receiver) = &mut this.receiver {
+            this.device.poll(wgpu::Maintain::Poll);
+            match Pin::new(receiver).poll(cx) {
+                Poll::Ready(Ok(bytes)) => bytes,
+                Poll::Ready(Err(oneshot::Canceled)) => panic!("GPU data retrieval failed"),
+                Poll::Pending => {
+                    // retrieval callbacks are only run when the device is polled on native platforms
+                    #[cfg(not(target_arch = "wasm32"))]
+                    cx.waker().wake_by_ref();
+                    return Poll::Pending;
+                }
+            }
+        } else {
+            vec![]
+        };
+        Poll::Ready(this.complete(bytes))
+    }
+}
@@ -0,0 +1,103 @@
+use crate::{wgsl, F32x2, F32x4, Gpu};
+
+pub(crate) use gpu::fullscreen_vertex;
+pub use gpu::{blur, grade_color, vignette};
+
+/// Returns the color of a pixel of the post-process source.
+///
+/// The source is the target rendered by the draws and the previous post-processes.
+/// `position` is the pixel position, with the origin at the top-left corner of the target.
+/// Positions outside the target are clamped to the target edges.
+///
+/// The returned value corresponds to RGBA components between `0.0` and `1.0`.
+///
+/// This function can only be called in fragment functions registered with
+/// [`App::with_post_process`](crate::App::with_post_process).
+pub fn source_color(position: F32x2) -> F32x4 {
+    crate::call_fn(wgsl::SOURCE_COLOR_FN_NAME, vec![position.value()], false)
+}
+
+#[crate::gpu]
+mod gpu {
+    use crate::{F32x2, F32x4, F32, U32};
+
+    pub(crate) fn fullscreen_vertex(vertex_index: U32, instance_index: U32) -> F32x4 {
+        // a single triangle covering the whole target
+        if vertex_index == 1u {
+            F32x4::new(3., 1., 0., 1.)
+        } else if vertex_index == 2u {
+            F32x4::new(-1., -3., 0., 1.)
+        } else {
+            F32x4::new(-1., 1., 0., 1.)
+        }
+    }
+
+    /// Returns the average color of the source pixels around a position.
+    ///
+    /// `position` is the pixel position, with the origin at the top-left corner of the target.
+    /// `radius` is the number of pixels taken into account in each direction.
+    ///
+    /// This function can only be called in fragment functions registered with
+    /// [`App::with_post_process`](crate::App::with_post_process).
+    pub fn blur(position: F32x2, radius: F32) -> F32x4 {
+        let sum = F32x4::new(0., 0., 0., 0.);
+        let count = 0.;
+        let offset_y = -radius;
+        while offset_y <= radius {
+            let offset_x = -radius;
+            while offset_x <= radius {
+                sum += super::source_color(position + F32x2::new(offset_x, offset_y));
+                count += 1.;
+                offset_x += 1.;
+            }
+            offset_y += 1.;
+        }
+        sum / F32x4::new(count, count, count, count)
+    }
+
+    /// Returns the source color at a position, darkened depending on the distance to the center
+    /// of the target.
+    ///
+    /// `position` is the pixel position, with the origin at the top-left corner of the target.
+    /// `strength` is the darkening factor between `0.0` and `1.0` applied to the corners.
+    ///
+    /// This function can only be called in fragment functions registered with
+    /// [`App::with_post_process`](crate::App::with_post_process).
+    pub fn vignette(position: F32x2, strength: F32) -> F32x4 {
+        let color = super::source_color(position);
+        let ndc = crate::screen_to_ndc(position);
+        let factor = 1. - strength * (ndc.x * ndc.x + ndc.y * ndc.y) / 2.;
+        F32x4::new(
+            color.x * factor,
+            color.y * factor,
+            color.z * factor,
+            color.w,
+        )
+    }
+
+    /// Returns a color with modified brightness, contrast and saturation.
+    ///
+    /// `brightness` is added to the RGB components, `contrast` scales the RGB components around
+    /// `0.5` and `saturation` scales the RGB components around the color luminance. The color is
+    /// unchanged with a brightness of `0.0`, a contrast of `1.0` and a saturation of `1.0`.
+    pub fn grade_color(color: F32x4, brightness: F32, contrast: F32, saturation: F32) -> F32x4 {
+        let luminance = 0.2126 * color.x + 0.7152 * color.y + 0.0722 * color.z;
+        F32x4::new(
+            grade_component(color.x, luminance, brightness, contrast, saturation),
+            grade_component(color.y, luminance, brightness, contrast, saturation),
+            grade_component(color.z, luminance, brightness, contrast, saturation),
+            color.w,
+        )
+    }
+
+    fn grade_component(
+        component: F32,
+        luminance: F32,
+        brightness: F32,
+        contrast: F32,
+        saturation: F32,
+    ) -> F32 {
+        let saturated = luminance + (component - luminance) * saturation;
+        (saturated - 0.5) * contrast + 0.5 + brightness
+    }
+}
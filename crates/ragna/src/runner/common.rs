@@ -1,17 +1,19 @@
+use crate::input::InputState;
 use crate::runner::program::Program;
-use crate::{App, GpuTypeDetails, GpuValue};
+use crate::time::StepTime;
+use crate::{target, App, GpuConfig, GpuValue, RagnaError, StepStats, TextureTargetConfig};
+use futures::channel::oneshot;
 use futures::executor;
 use std::sync::Arc;
-use std::time::{Duration, Instant};
+use std::time::Instant;
 use wgpu::{
-    Adapter, BackendOptions, Backends, BufferDescriptor, BufferUsages, Color, CommandEncoder,
+    Adapter, BackendOptions, Buffer, BufferDescriptor, BufferUsages, Color, CommandEncoder,
     CommandEncoderDescriptor, ComputePass, ComputePassDescriptor, Device, DeviceDescriptor,
-    Extent3d, Features, Instance, InstanceFlags, Limits, LoadOp, MapMode, MemoryHints, Operations,
-    PowerPreference, Queue, RenderPass, RenderPassColorAttachment,
-    RenderPassDepthStencilAttachment, RenderPassDescriptor, RequestAdapterOptions, StoreOp,
-    Surface, SurfaceConfiguration, SurfaceTexture, TexelCopyBufferInfo, TexelCopyBufferLayout,
-    Texture, TextureDescriptor, TextureDimension, TextureFormat, TextureUsages, TextureView,
-    TextureViewDescriptor,
+    Extent3d, Instance, InstanceFlags, LoadOp, MapMode, MemoryHints, Operations, Queue, RenderPass,
+    RenderPassColorAttachment, RenderPassDepthStencilAttachment, RenderPassDescriptor,
+    RequestAdapterOptions, StoreOp, SubmissionIndex, Surface, SurfaceConfiguration, SurfaceTexture,
+    TexelCopyBufferInfo, TexelCopyBufferLayout, Texture, TextureDescriptor, TextureDimension,
+    TextureFormat, TextureUsages, TextureView, TextureViewDescriptor,
 };
 use winit::dpi::PhysicalSize;
 use winit::event_loop::ActiveEventLoop;
@@ -21,33 +23,46 @@ use winit::window::Window;
 pub(crate) struct Runner {
     pub(crate) target: Target,
     instance: Instance,
-    device: Device,
+    pub(crate) device: Device,
     adapter: Adapter,
     queue: Queue,
     program: Program,
     is_started: bool,
-    last_delta: Duration,
-    last_step_end: Instant,
+    pub(crate) stats: StepStats,
+    pub(crate) time: StepTime,
+    pub(crate) input: InputState,
 }
 
 impl Runner {
-    pub(crate) fn new_texture(app: &App, size: (u32, u32)) -> Self {
+    pub(crate) fn new_texture(
+        app: &App,
+        size: (u32, u32),
+        texture_config: TextureTargetConfig,
+    ) -> Result<Self, RagnaError> {
         let target = TargetConfig {
             size: (size.0.max(1), size.1.max(1)),
             ..Default::default()
         };
-        let instance = Self::create_instance();
-        let adapter = Self::create_adapter(&instance, None);
-        let (device, queue) = Self::create_device(&adapter);
-        let texture = Self::create_target_texture(&device, target.size);
+        let format = texture_config.format.to_wgpu();
+        let samples = texture_config.samples;
+        let instance = Self::create_instance(&app.gpu_config);
+        let adapter = Self::create_adapter(&instance, None, &app.gpu_config)?;
+        let (device, queue) = Self::create_device(&adapter, &app.gpu_config)?;
+        if !Program::is_sample_count_supported(&adapter, &device, format, samples) {
+            return Err(RagnaError::UnsupportedSampleCount { samples });
+        }
+        let texture = Self::create_target_texture(&device, target.size, format);
         let view = texture.create_view(&TextureViewDescriptor::default());
-        let depth_buffer = Self::create_depth_buffer(&device, target.size);
-        let program = Program::new(app, &device);
-        Self {
+        let multisample_buffer = (samples > 1)
+            .then(|| Self::create_multisample_buffer(&device, target.size, format, samples));
+        let depth_buffer = Program::create_depth_buffer(&device, target.size, samples);
+        let program = Program::new(app, &device, &queue, format, target.size, samples)?;
+        Ok(Self {
             target: Target {
                 inner: TargetSpecialized::Texture(TextureTarget { texture, view }),
                 config: target,
                 depth_buffer,
+                multisample_buffer,
             },
             instance,
             device,
@@ -55,9 +70,10 @@ impl Runner {
             queue,
             program,
             is_started: false,
-            last_delta: Duration::ZERO,
-            last_step_end: Instant::now(),
-        }
+            stats: StepStats::default(),
+            time: StepTime::default(),
+            input: InputState::default(),
+        })
     }
 
     // coverage: off (window cannot be tested)
@@ -65,20 +81,20 @@ impl Runner {
         app: &App,
         event_loop: &ActiveEventLoop,
         background_color: Color,
-    ) -> Self {
+    ) -> Result<Self, RagnaError> {
         let target = TargetConfig {
             background_color,
             ..Default::default()
         };
-        let instance = Self::create_instance();
+        let instance = Self::create_instance(&app.gpu_config);
         let window = Self::create_window(event_loop, target.size);
         let surface = Self::create_surface(&instance, window.clone());
-        let adapter = Self::create_adapter(&instance, Some(&surface));
-        let (device, queue) = Self::create_device(&adapter);
+        let adapter = Self::create_adapter(&instance, Some(&surface), &app.gpu_config)?;
+        let (device, queue) = Self::create_device(&adapter, &app.gpu_config)?;
         let surface_config = Self::create_surface_config(&adapter, &device, &surface, target.size);
-        let depth_buffer = Self::create_depth_buffer(&device, target.size);
-        let program = Program::new(app, &device);
-        Self {
+        let depth_buffer = Program::create_depth_buffer(&device, target.size, 1);
+        let program = Program::new(app, &device, &queue, surface_config.format, target.size, 1)?;
+        Ok(Self {
             target: Target {
                 inner: TargetSpecialized::Window(WindowTarget {
                     window,
@@ -87,6 +103,7 @@ impl Runner {
                 }),
                 config: target,
                 depth_buffer,
+                multisample_buffer: None,
             },
             instance,
             device,
@@ -94,21 +111,39 @@ impl Runner {
             queue,
             program,
             is_started: false,
-            last_delta: Duration::ZERO,
-            last_step_end: Instant::now(),
-        }
+            stats: StepStats::default(),
+            time: StepTime::default(),
+            input: InputState::default(),
+        })
     }
     // coverage: on
 
-    #[allow(clippy::print_stdout)]
-    pub(crate) fn run_step(&mut self) {
-        let start = Instant::now();
-        let mut encoder = self.create_encoder();
+    // globs are initialized in a separate submission so that they can be written before the first step
+    pub(crate) fn start(&mut self) {
         if !self.is_started {
+            // default values may depend on target properties
+            self.write_target_globs();
+            let mut encoder = self.create_encoder();
             let pass = Self::create_compute_pass(&mut encoder);
             self.program.run_init(pass);
+            self.queue.submit(Some(encoder.finish()));
             self.is_started = true;
         }
+    }
+
+    pub(crate) fn run_step(&mut self) {
+        let start = Instant::now();
+        self.time.start_step();
+        for (glob, bytes) in self.time.glob_bytes() {
+            self.write(&glob, &bytes);
+        }
+        for (glob, bytes) in self.input.glob_bytes() {
+            self.write(&glob, &bytes);
+        }
+        self.input.end_step();
+        self.write_target_globs();
+        self.start();
+        let mut encoder = self.create_encoder();
         let pass = Self::create_compute_pass(&mut encoder);
         self.program.run_update_step(pass);
         match &self.target.inner {
@@ -116,100 +151,289 @@ impl Runner {
             TargetSpecialized::Window(target) => {
                 let texture = target.create_surface_texture();
                 let view = Self::create_surface_view(&texture);
-                let pass = Self::create_render_pass(
-                    &mut encoder,
-                    &view,
-                    &self.target.depth_buffer,
-                    self.target.config.background_color,
-                );
-                self.program.run_draw_step(pass);
+                self.run_draw_step(&mut encoder, &view);
                 self.queue.submit(Some(encoder.finish()));
                 texture.present();
             }
             // coverage: on
             TargetSpecialized::Texture(target) => {
-                let pass = Self::create_render_pass(
-                    &mut encoder,
-                    &target.view,
-                    &self.target.depth_buffer,
-                    self.target.config.background_color,
-                );
-                self.program.run_draw_step(pass);
+                self.run_draw_step(&mut encoder, &target.view);
                 self.queue.submit(Some(encoder.finish()));
             }
         }
-        let end = Instant::now();
-        self.last_delta = end - start;
-        self.last_step_end = end;
-        println!(
-            "Step duration: {}µs ({}fps)",
-            self.last_delta.as_micros(),
-            (1. / self.last_delta.as_secs_f32()).round()
+        self.stats.register_step(start.elapsed());
+    }
+
+    fn run_draw_step(&self, encoder: &mut CommandEncoder, view: &TextureView) {
+        for (index, target) in self.program.offscreen_targets.iter().enumerate() {
+            let pass = Self::create_render_pass(
+                encoder,
+                &target.view,
+                None,
+                &target.depth_buffer,
+                Color::TRANSPARENT,
+            );
+            self.program.run_offscreen_draw_step(index, pass);
+        }
+        let scene_view = self.program.post_process_source_view(0).unwrap_or(view);
+        let pass = Self::create_render_pass(
+            encoder,
+            scene_view,
+            self.target.multisample_buffer.as_ref(),
+            &self.target.depth_buffer,
+            self.target.config.background_color,
         );
+        self.program.run_draw_step(pass);
+        for index in 0..self.program.post_process_count() {
+            let output_view = self
+                .program
+                .post_process_source_view(index + 1)
+                .unwrap_or(view);
+            let pass = Self::create_post_process_pass(encoder, output_view);
+            self.program.run_post_process_step(index, pass);
+        }
     }
 
-    pub(crate) fn read(&self, app: &App, value: &GpuValue) -> Vec<u8> {
+    fn write_target_globs(&self) {
+        for (glob, bytes) in target::glob_bytes(self.target.config.size, self.target.scale_factor())
+        {
+            self.write(&glob, &bytes);
+        }
+    }
+
+    pub(crate) fn write(&self, value: &GpuValue, bytes: &[u8]) {
+        self.write_range(value, 0, bytes);
+    }
+
+    // `offset` is relative to the start of the value
+    pub(crate) fn write_range(&self, value: &GpuValue, offset: u64, bytes: &[u8]) {
         if let Some(buffer) = &self.program.buffer {
-            if let Some(position) = app.globs.iter().position(|other_glob| other_glob == value) {
-                let buffer_type_details = GpuTypeDetails::from_fields(&app.globs, &app.types);
-                let value_size = app.types[&value.type_id].1.size();
-                let tmp_buffer = self.device.create_buffer(&BufferDescriptor {
-                    label: Some("ragna:glob_buffer"),
-                    size: value_size,
-                    usage: BufferUsages::MAP_READ | BufferUsages::COPY_DST,
-                    mapped_at_creation: false,
-                });
-                let mut encoder = self
-                    .device
-                    .create_command_encoder(&CommandEncoderDescriptor {
-                        label: Some("ragna:glob_buffer_retrieval"),
-                    });
+            if let Some(glob_offset) = self.program.glob_offset(value) {
+                self.queue.write_buffer(buffer, glob_offset + offset, bytes);
+            }
+        }
+    }
+
+    pub(crate) fn read(&self, app: &App, value: &GpuValue) -> Vec<u8> {
+        self.read_many(app, &[*value]).remove(0)
+    }
+
+    pub(crate) fn read_many(&self, app: &App, values: &[GpuValue]) -> Vec<Vec<u8>> {
+        let Some(buffer) = &self.program.buffer else {
+            return vec![vec![]; values.len()];
+        };
+        let mut staging_size = 0;
+        // (buffer offset, staging buffer offset, size) of each registered value
+        let ranges: Vec<_> = values
+            .iter()
+            .map(|value| {
+                let offset = self.program.glob_offset(value)?;
+                let size = app.types[&value.type_id].1.size();
+                let range = (offset, staging_size, size);
+                staging_size += size;
+                Some(range)
+            })
+            .collect();
+        if staging_size == 0 {
+            return vec![vec![]; values.len()];
+        }
+        let content = self.read_buffer(
+            "glob",
+            staging_size,
+            |encoder, staging_buffer| {
+                for &(offset, staging_offset, size) in ranges.iter().flatten() {
+                    encoder.copy_buffer_to_buffer(
+                        buffer,
+                        offset,
+                        staging_buffer,
+                        staging_offset,
+                        size,
+                    );
+                }
+            },
+            <[u8]>::to_vec,
+        );
+        ranges
+            .into_iter()
+            .map(|range| {
+                range.map_or_else(Vec::new, |(_, staging_offset, size)| {
+                    Self::slice_range(&content, staging_offset, size).to_vec()
+                })
+            })
+            .collect()
+    }
+
+    // `offset` is relative to the start of the value
+    pub(crate) fn read_range(&self, value: &GpuValue, offset: u64, size: u64) -> Option<Vec<u8>> {
+        let buffer = self.program.buffer.as_ref()?;
+        let glob_offset = self.program.glob_offset(value)?;
+        if size == 0 {
+            return Some(vec![]);
+        }
+        Some(self.read_buffer(
+            "glob",
+            size,
+            |encoder, staging_buffer| {
                 encoder.copy_buffer_to_buffer(
                     buffer,
-                    buffer_type_details.field_offset(position),
-                    &tmp_buffer,
+                    glob_offset + offset,
+                    staging_buffer,
                     0,
-                    value_size,
+                    size,
                 );
-                let submission_index = self.queue.submit(Some(encoder.finish()));
-                let slice = tmp_buffer.slice(..);
-                slice.map_async(MapMode::Read, |_| ());
-                self.device
-                    .poll(wgpu::Maintain::WaitForSubmissionIndex(submission_index));
-                let view = slice.get_mapped_range();
-                let content = view.to_vec();
-                drop(view);
-                tmp_buffer.unmap();
-                content
-            } else {
-                vec![]
-            }
+            },
+            <[u8]>::to_vec,
+        ))
+    }
+
+    pub(crate) fn read_async(
+        &self,
+        app: &App,
+        value: &GpuValue,
+    ) -> Option<oneshot::Receiver<Vec<u8>>> {
+        let buffer = self.program.buffer.as_ref()?;
+        let offset = self.program.glob_offset(value)?;
+        let size = app.types[&value.type_id].1.size();
+        let (_, receiver) = self.read_buffer_async(
+            "glob",
+            size,
+            |encoder, staging_buffer| {
+                encoder.copy_buffer_to_buffer(buffer, offset, staging_buffer, 0, size);
+            },
+            <[u8]>::to_vec,
+        );
+        Some(receiver)
+    }
+
+    pub(crate) fn read_globs(&self) -> Vec<u8> {
+        if let Some(buffer) = &self.program.buffer {
+            self.read_buffer(
+                "glob",
+                buffer.size(),
+                |encoder, staging_buffer| {
+                    encoder.copy_buffer_to_buffer(buffer, 0, staging_buffer, 0, buffer.size());
+                },
+                <[u8]>::to_vec,
+            )
         } else {
             vec![]
         }
     }
 
+    fn read_buffer(
+        &self,
+        name: &str,
+        size: u64,
+        copy: impl FnOnce(&mut CommandEncoder, &Buffer),
+        extract: impl FnOnce(&[u8]) -> Vec<u8> + Send + 'static,
+    ) -> Vec<u8> {
+        let (submission_index, receiver) = self.read_buffer_async(name, size, copy, extract);
+        self.device
+            .poll(wgpu::Maintain::WaitForSubmissionIndex(submission_index));
+        executor::block_on(receiver).expect("internal error: GPU buffer not mapped")
+    }
+
+    // the content is sent once the GPU has copied it and the device has been polled
+    fn read_buffer_async(
+        &self,
+        name: &str,
+        size: u64,
+        copy: impl FnOnce(&mut CommandEncoder, &Buffer),
+        extract: impl FnOnce(&[u8]) -> Vec<u8> + Send + 'static,
+    ) -> (SubmissionIndex, oneshot::Receiver<Vec<u8>>) {
+        let tmp_buffer = self.device.create_buffer(&BufferDescriptor {
+            label: Some(&format!("ragna:{name}_buffer")),
+            size,
+            usage: BufferUsages::MAP_READ | BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+        let mut encoder = self
+            .device
+            .create_command_encoder(&CommandEncoderDescriptor {
+                label: Some(&format!("ragna:{name}_buffer_retrieval")),
+            });
+        copy(&mut encoder, &tmp_buffer);
+        let submission_index = self.queue.submit(Some(encoder.finish()));
+        let (sender, receiver) = oneshot::channel();
+        let mapped_buffer = tmp_buffer.clone();
+        tmp_buffer
+            .slice(..)
+            .map_async(MapMode::Read, move |result| {
+                if result.is_ok() {
+                    let view = mapped_buffer.slice(..).get_mapped_range();
+                    // the receiver is dropped if the read is not awaited anymore
+                    let _ = sender.send(extract(&view));
+                    drop(view);
+                    mapped_buffer.unmap();
+                }
+            });
+        (submission_index, receiver)
+    }
+
+    #[allow(clippy::cast_possible_truncation)]
+    fn slice_range(bytes: &[u8], offset: u64, size: u64) -> &[u8] {
+        &bytes[offset as usize..(offset + size) as usize]
+    }
+
     pub(crate) fn read_target(&self) -> Vec<u8> {
+        let (submission_index, receiver) = self.submit_target_read();
+        self.device
+            .poll(wgpu::Maintain::WaitForSubmissionIndex(submission_index));
+        executor::block_on(receiver).expect("internal error: GPU buffer not mapped")
+    }
+
+    pub(crate) fn read_target_async(&self) -> oneshot::Receiver<Vec<u8>> {
+        let (_, receiver) = self.submit_target_read();
+        receiver
+    }
+
+    fn submit_target_read(&self) -> (SubmissionIndex, oneshot::Receiver<Vec<u8>>) {
         match &self.target.inner {
             TargetSpecialized::Texture(target) => {
-                let size = self.target.config.size;
-                let padded_bytes_per_row = Self::calculate_padded_row_bytes(size.0);
-                let padded_row_bytes = Self::calculate_padded_row_bytes(size.0);
-                let tmp_buffer = self.device.create_buffer(&BufferDescriptor {
-                    label: Some("ragna:texture_buffer"),
-                    size: (padded_bytes_per_row * size.1).into(),
-                    usage: BufferUsages::MAP_READ | BufferUsages::COPY_DST,
-                    mapped_at_creation: false,
-                });
-                let mut encoder = self
-                    .device
-                    .create_command_encoder(&CommandEncoderDescriptor {
-                        label: Some("ragna:texture_buffer_retrieval"),
-                    });
+                self.read_texture_async(&target.texture, self.target.config.size)
+            }
+            TargetSpecialized::Window(_) => {
+                unreachable!("internal error: cannot read window buffer")
+            }
+        }
+    }
+
+    pub(crate) fn read_post_process_source(&self, index: usize) -> Option<Vec<u8>> {
+        self.program
+            .post_process_textures
+            .get(index)
+            .map(|(texture, _)| self.read_texture(texture, self.target.config.size))
+    }
+
+    pub(crate) fn read_offscreen_target(&self, index: usize) -> (Vec<u8>, TextureFormat) {
+        let texture = &self.program.offscreen_targets[index].texture;
+        let buffer = self.read_texture(texture, (texture.width(), texture.height()));
+        (buffer, texture.format())
+    }
+
+    fn read_texture(&self, texture: &Texture, size: (u32, u32)) -> Vec<u8> {
+        let (submission_index, receiver) = self.read_texture_async(texture, size);
+        self.device
+            .poll(wgpu::Maintain::WaitForSubmissionIndex(submission_index));
+        executor::block_on(receiver).expect("internal error: GPU buffer not mapped")
+    }
+
+    fn read_texture_async(
+        &self,
+        texture: &Texture,
+        size: (u32, u32),
+    ) -> (SubmissionIndex, oneshot::Receiver<Vec<u8>>) {
+        let pixel_size = texture.format().block_copy_size(None).unwrap_or(4);
+        let padded_row_bytes = Self::calculate_padded_row_bytes(size.0, pixel_size);
+        let unpadded_row_bytes = size.0 * pixel_size;
+        self.read_buffer_async(
+            "texture",
+            (padded_row_bytes * size.1).into(),
+            |encoder, staging_buffer| {
                 encoder.copy_texture_to_buffer(
-                    target.texture.as_image_copy(),
+                    texture.as_image_copy(),
                     TexelCopyBufferInfo {
-                        buffer: &tmp_buffer,
+                        buffer: staging_buffer,
                         layout: TexelCopyBufferLayout {
                             offset: 0,
                             bytes_per_row: Some(padded_row_bytes),
@@ -222,27 +446,15 @@ impl Runner {
                         depth_or_array_layers: 1,
                     },
                 );
-                let submission_index = self.queue.submit(Some(encoder.finish()));
-                let slice = tmp_buffer.slice(..);
-                slice.map_async(MapMode::Read, |_| ());
-                self.device
-                    .poll(wgpu::Maintain::WaitForSubmissionIndex(submission_index));
-                let view = slice.get_mapped_range();
-                let padded_row_bytes = Self::calculate_padded_row_bytes(size.0);
-                let unpadded_row_bytes = Self::calculate_unpadded_row_bytes(size.0);
-                let content = view
+            },
+            move |content| {
+                content
                     .chunks(padded_row_bytes as usize)
-                    .flat_map(|a| &a[..unpadded_row_bytes as usize])
+                    .flat_map(|row| &row[..unpadded_row_bytes as usize])
                     .copied()
-                    .collect();
-                drop(view);
-                tmp_buffer.unmap();
-                content
-            }
-            TargetSpecialized::Window(_) => {
-                unreachable!("internal error: cannot read window buffer")
-            }
-        }
+                    .collect()
+            },
+        )
     }
 
     // coverage: off (window cannot be tested)
@@ -268,7 +480,9 @@ impl Runner {
             TargetSpecialized::Window(target) => {
                 self.target.config.size = (size.width.max(1), size.height.max(1));
                 self.target.depth_buffer =
-                    Self::create_depth_buffer(&self.device, self.target.config.size);
+                    Program::create_depth_buffer(&self.device, self.target.config.size, 1);
+                self.program
+                    .update_target_size(&self.device, self.target.config.size);
                 target.surface_config = Self::create_surface_config(
                     &self.adapter,
                     &self.device,
@@ -283,46 +497,47 @@ impl Runner {
     }
     // coverage: on
 
-    fn calculate_padded_row_bytes(width: u32) -> u32 {
-        let unpadded_bytes_per_row = Self::calculate_unpadded_row_bytes(width);
+    fn calculate_padded_row_bytes(width: u32, pixel_size: u32) -> u32 {
+        let unpadded_bytes_per_row = width * pixel_size;
         let align = wgpu::COPY_BYTES_PER_ROW_ALIGNMENT;
         let padded_bytes_per_row_padding = (align - unpadded_bytes_per_row % align) % align;
         unpadded_bytes_per_row + padded_bytes_per_row_padding
     }
 
-    #[allow(clippy::cast_possible_truncation)]
-    fn calculate_unpadded_row_bytes(width: u32) -> u32 {
-        let bytes_per_pixel = size_of::<u32>() as u32;
-        width * bytes_per_pixel
-    }
-
-    fn create_instance() -> Instance {
+    fn create_instance(config: &GpuConfig) -> Instance {
         Instance::new(&wgpu::InstanceDescriptor {
-            backends: Backends::from_env().unwrap_or_else(Backends::all),
+            backends: config.backends,
             flags: InstanceFlags::default(),
             backend_options: BackendOptions::default(),
         })
     }
 
-    fn create_adapter(instance: &Instance, surface: Option<&Surface<'_>>) -> Adapter {
+    fn create_adapter(
+        instance: &Instance,
+        surface: Option<&Surface<'_>>,
+        config: &GpuConfig,
+    ) -> Result<Adapter, RagnaError> {
         let adapter_request = RequestAdapterOptions {
-            power_preference: PowerPreference::default(),
-            force_fallback_adapter: false,
+            power_preference: config.power_preference,
+            force_fallback_adapter: config.force_fallback_adapter,
             compatible_surface: surface,
         };
         executor::block_on(instance.request_adapter(&adapter_request))
-            .expect("no supported graphic adapter found")
+            .ok_or(RagnaError::AdapterNotFound)
     }
 
-    fn create_device(adapter: &Adapter) -> (Device, Queue) {
+    fn create_device(adapter: &Adapter, config: &GpuConfig) -> Result<(Device, Queue), RagnaError> {
         let device_descriptor = DeviceDescriptor {
             label: None,
-            required_features: Features::default(),
-            required_limits: Limits::default(),
+            required_features: config.required_features,
+            required_limits: config.required_limits.clone(),
             memory_hints: MemoryHints::Performance,
         };
-        executor::block_on(adapter.request_device(&device_descriptor, None))
-            .expect("error when retrieving graphic device")
+        executor::block_on(adapter.request_device(&device_descriptor, None)).map_err(|error| {
+            RagnaError::DeviceRequestFailed {
+                message: error.to_string(),
+            }
+        })
     }
 
     // coverage: off (window cannot be tested)
@@ -360,7 +575,7 @@ impl Runner {
     }
     // coverage: on
 
-    fn create_target_texture(device: &Device, size: (u32, u32)) -> Texture {
+    fn create_target_texture(device: &Device, size: (u32, u32), format: TextureFormat) -> Texture {
         device.create_texture(&TextureDescriptor {
             label: Some("ragna:target_texture"),
             size: Extent3d {
@@ -371,24 +586,29 @@ impl Runner {
             mip_level_count: 1,
             sample_count: 1,
             dimension: TextureDimension::D2,
-            format: TextureFormat::Rgba8UnormSrgb,
+            format,
             usage: TextureUsages::RENDER_ATTACHMENT | TextureUsages::COPY_SRC,
             view_formats: &[],
         })
     }
 
-    fn create_depth_buffer(device: &Device, size: (u32, u32)) -> TextureView {
+    fn create_multisample_buffer(
+        device: &Device,
+        size: (u32, u32),
+        format: TextureFormat,
+        samples: u32,
+    ) -> TextureView {
         let texture = device.create_texture(&TextureDescriptor {
-            label: Some("ragna:depth_texture"),
+            label: Some("ragna:multisample_texture"),
             size: Extent3d {
                 width: size.0,
                 height: size.1,
                 depth_or_array_layers: 1,
             },
             mip_level_count: 1,
-            sample_count: 1,
+            sample_count: samples,
             dimension: TextureDimension::D2,
-            format: TextureFormat::Depth32Float,
+            format,
             usage: TextureUsages::RENDER_ATTACHMENT,
             view_formats: &[],
         });
@@ -409,17 +629,43 @@ impl Runner {
         })
     }
 
+    fn create_post_process_pass<'a>(
+        encoder: &'a mut CommandEncoder,
+        view: &'a TextureView,
+    ) -> RenderPass<'a> {
+        encoder.begin_render_pass(&RenderPassDescriptor {
+            label: Some("ragna:post_process_pass"),
+            color_attachments: &[Some(RenderPassColorAttachment {
+                view,
+                resolve_target: None,
+                ops: Operations {
+                    load: LoadOp::Clear(Color::TRANSPARENT),
+                    store: StoreOp::Store,
+                },
+            })],
+            depth_stencil_attachment: None,
+            timestamp_writes: None,
+            occlusion_query_set: None,
+        })
+    }
+
     fn create_render_pass<'a>(
         encoder: &'a mut CommandEncoder,
         view: &'a TextureView,
+        multisample_buffer: Option<&'a TextureView>,
         depth_buffer: &'a TextureView,
         background_color: Color,
     ) -> RenderPass<'a> {
+        // with multisampling, the draws are rendered in the multisample buffer and resolved in view
+        let (view, resolve_target) = match multisample_buffer {
+            Some(buffer) => (buffer, Some(view)),
+            None => (view, None),
+        };
         encoder.begin_render_pass(&RenderPassDescriptor {
             label: Some("ragna:render_pass"),
             color_attachments: &[Some(RenderPassColorAttachment {
                 view,
-                resolve_target: None,
+                resolve_target,
                 ops: Operations {
                     load: LoadOp::Clear(background_color),
                     store: StoreOp::Store,
@@ -444,6 +690,19 @@ pub(crate) struct Target {
     pub(crate) inner: TargetSpecialized,
     pub(crate) config: TargetConfig,
     depth_buffer: TextureView,
+    multisample_buffer: Option<TextureView>,
+}
+
+impl Target {
+    #[allow(clippy::cast_possible_truncation)]
+    fn scale_factor(&self) -> f32 {
+        match &self.inner {
+            // coverage: off (window cannot be tested)
+            TargetSpecialized::Window(target) => target.window.scale_factor() as f32,
+            // coverage: on
+            TargetSpecialized::Texture(_) => 1.,
+        }
+    }
 }
 
 #[derive(Debug)]
@@ -1,33 +1,188 @@
-use crate::{App, GpuTypeDetails};
+use crate::draw::Draw;
+use crate::texture::TextureSource;
+use crate::{
+    wgsl, App, DrawConfig, GpuTypeDetails, GpuValue, Image, RagnaError, SamplerConfig,
+    TextureFilter, TextureWrap,
+};
+use futures::executor;
+use fxhash::FxHashMap;
+use itertools::Itertools;
+use wgpu::util::{DeviceExt, TextureDataOrder};
 use wgpu::{
-    BindGroup, BindGroupEntry, Buffer, BufferDescriptor, BufferUsages, ComputePass,
-    ComputePipeline, ComputePipelineDescriptor, Device, RenderPass, ShaderModuleDescriptor,
+    Adapter, AddressMode, BindGroup, BindGroupDescriptor, BindGroupEntry, BindGroupLayout,
+    BindGroupLayoutDescriptor, BindGroupLayoutEntry, BindingResource, BindingType, BlendState,
+    Buffer, BufferBindingType, BufferDescriptor, BufferUsages, ColorTargetState, ColorWrites,
+    CompareFunction, CompilationMessageType, ComputePass, ComputePipeline,
+    ComputePipelineDescriptor, DepthBiasState, DepthStencilState, Device, ErrorFilter, Extent3d,
+    Features, FilterMode, FragmentState, MultisampleState, PipelineLayout,
+    PipelineLayoutDescriptor, PrimitiveState, Queue, RenderPass, RenderPipeline,
+    RenderPipelineDescriptor, Sampler, SamplerBindingType, SamplerDescriptor, ShaderModule,
+    ShaderModuleDescriptor, ShaderStages, StencilState, StorageTextureAccess, Texture,
+    TextureDescriptor, TextureDimension, TextureFormat, TextureSampleType, TextureUsages,
+    TextureView, TextureViewDescriptor, TextureViewDimension, VertexState,
 };
 
+const DEPTH_BUFFER_FORMAT: TextureFormat = TextureFormat::Depth32Float;
+const PIXEL_TEXTURE_FORMAT: TextureFormat = TextureFormat::Rgba8Unorm;
+const IMAGE_TEXTURE_FORMAT: TextureFormat = TextureFormat::Rgba8UnormSrgb;
+const WGSL_EXCERPT_LINE_MARGIN: usize = 3;
+
 #[derive(Debug)]
 pub(crate) struct Program {
     init_shader: ComputeShader,
     step_shaders: Vec<ComputeShader>,
+    pixel_shader: Option<RenderShader>,
+    draw_shaders: Vec<RenderShader>,
+    post_process_shaders: Vec<RenderShader>,
+    pub(crate) offscreen_targets: Vec<OffscreenTargetPass>,
     pub(crate) buffer: Option<Buffer>,
+    glob_offsets: FxHashMap<GpuValue, u64>,
+    pixel_texture: Option<TextureView>,
+    textures: Vec<(TextureView, Sampler)>,
+    pub(crate) post_process_textures: Vec<(Texture, TextureView)>,
+    target_format: TextureFormat,
 }
 
 impl Program {
-    pub(crate) fn new(app: &App, device: &Device) -> Self {
-        let buffer = Self::create_buffer(app, device);
-        let bind_group_entry = Self::create_bind_group_entry(buffer.as_ref());
-        Self {
-            init_shader: ComputeShader::new(
-                app.wgsl_init_shader(),
+    pub(crate) fn new(
+        app: &App,
+        device: &Device,
+        queue: &Queue,
+        target_format: TextureFormat,
+        target_size: (u32, u32),
+        sample_count: u32,
+    ) -> Result<Self, RagnaError> {
+        Self::check_texture_count(app, device)?;
+        let buffer = Self::create_buffer(app, device)?;
+        let glob_offsets = Self::glob_offsets(app);
+        let (init_shader_code, constant_globs) = app.wgsl_init_shader();
+        if let Some(buffer) = &buffer {
+            for (glob, bytes) in constant_globs {
+                if let Some(&offset) = glob_offsets.get(&glob) {
+                    queue.write_buffer(buffer, offset, &bytes);
+                }
+            }
+        }
+        let pixel_texture = app
+            .is_using_pixels()
+            .then(|| Self::create_pixel_texture(device, target_size));
+        let (raw_textures, textures) = Self::create_textures(app, device, queue);
+        let (init_shader, step_shaders) = Self::create_compute_shaders(
+            app,
+            device,
+            init_shader_code,
+            buffer.as_ref(),
+            pixel_texture.as_ref(),
+            &textures,
+        )?;
+        let draw_layout = Self::create_bind_group_layout(
+            device,
+            ShaderStages::VERTEX_FRAGMENT,
+            buffer.as_ref(),
+            None,
+            textures.len(),
+        );
+        let draw_entries = Self::compute_bind_group_entries(buffer.as_ref(), None, &textures);
+        let draw_bindings = Bindings {
+            layout: &draw_layout,
+            entries: &draw_entries,
+        };
+        let offscreen_targets = Self::create_offscreen_targets(
+            app,
+            device,
+            &draw_layout,
+            buffer.as_ref(),
+            &raw_textures,
+            &textures,
+        )?;
+        let post_process_textures: Vec<_> = (0..app.post_processes.len())
+            .map(|_| Self::create_post_process_texture(device, target_format, target_size))
+            .collect();
+        Ok(Self {
+            init_shader,
+            step_shaders,
+            pixel_shader: pixel_texture
+                .as_ref()
+                .map(|view| {
+                    Self::create_pixel_shader(app, device, view, target_format, sample_count)
+                })
+                .transpose()?,
+            draw_shaders: app
+                .wgsl_draw_shaders()
+                .map(|(code, (draw, config))| {
+                    RenderShader::new(
+                        code,
+                        draw,
+                        Some(*config),
+                        device,
+                        &draw_bindings,
+                        target_format,
+                        sample_count,
+                    )
+                })
+                .collect::<Result<_, _>>()?,
+            post_process_shaders: Self::create_post_process_shaders(
+                app,
                 device,
-                bind_group_entry.clone(),
-            ),
-            step_shaders: app
-                .wgsl_update_shaders()
-                .map(|code| ComputeShader::new(code, device, bind_group_entry.clone()))
-                .collect(),
+                buffer.as_ref(),
+                &textures,
+                &post_process_textures,
+                target_format,
+            )?,
+            offscreen_targets,
             buffer,
+            glob_offsets,
+            pixel_texture,
+            textures,
+            post_process_textures,
+            target_format,
+        })
+    }
+
+    // coverage: off (window cannot be tested)
+    pub(crate) fn update_target_size(&mut self, device: &Device, size: (u32, u32)) {
+        self.update_pixel_texture_size(device, size);
+        self.update_post_process_texture_size(device, size);
+    }
+
+    fn update_pixel_texture_size(&mut self, device: &Device, size: (u32, u32)) {
+        if self.pixel_texture.is_none() {
+            return;
+        }
+        let pixel_texture = Self::create_pixel_texture(device, size);
+        let compute_entries = Self::compute_bind_group_entries(
+            self.buffer.as_ref(),
+            Some(&pixel_texture),
+            &self.textures,
+        );
+        self.init_shader.update_bind_group(device, &compute_entries);
+        for shader in &mut self.step_shaders {
+            shader.update_bind_group(device, &compute_entries);
+        }
+        if let Some(shader) = &mut self.pixel_shader {
+            shader.update_bind_group(device, &[Self::create_texture_entry(0, &pixel_texture)]);
+        }
+        self.pixel_texture = Some(pixel_texture);
+    }
+
+    fn update_post_process_texture_size(&mut self, device: &Device, size: (u32, u32)) {
+        self.post_process_textures = (0..self.post_process_shaders.len())
+            .map(|_| Self::create_post_process_texture(device, self.target_format, size))
+            .collect();
+        for (shader, (_, source_view)) in self
+            .post_process_shaders
+            .iter_mut()
+            .zip(&self.post_process_textures)
+        {
+            let entries = Self::compute_bind_group_entries(
+                self.buffer.as_ref(),
+                Some(source_view),
+                &self.textures,
+            );
+            shader.update_bind_group(device, &entries);
         }
     }
+    // coverage: on
 
     pub(crate) fn run_init(&self, mut pass: ComputePass<'_>) {
         pass.set_pipeline(&self.init_shader.pipeline);
@@ -43,28 +198,556 @@ impl Program {
         }
     }
 
-    #[allow(clippy::unused_self)]
-    pub(crate) fn run_draw_step(&self, _pass: RenderPass<'_>) {
-        // do nothing for the moment
+    pub(crate) fn run_draw_step(&self, mut pass: RenderPass<'_>) {
+        for shader in self.pixel_shader.iter().chain(&self.draw_shaders) {
+            pass.set_pipeline(&shader.pipeline);
+            pass.set_bind_group(0, &shader.bind_group, &[]);
+            pass.draw(0..shader.vertex_count, 0..shader.instance_count);
+        }
     }
 
-    fn create_buffer(app: &App, device: &Device) -> Option<Buffer> {
+    pub(crate) fn run_offscreen_draw_step(&self, index: usize, mut pass: RenderPass<'_>) {
+        for shader in &self.offscreen_targets[index].shaders {
+            pass.set_pipeline(&shader.pipeline);
+            pass.set_bind_group(0, &shader.bind_group, &[]);
+            pass.draw(0..shader.vertex_count, 0..shader.instance_count);
+        }
+    }
+
+    pub(crate) fn post_process_count(&self) -> usize {
+        self.post_process_shaders.len()
+    }
+
+    pub(crate) fn post_process_source_view(&self, index: usize) -> Option<&TextureView> {
+        self.post_process_textures.get(index).map(|(_, view)| view)
+    }
+
+    pub(crate) fn run_post_process_step(&self, index: usize, mut pass: RenderPass<'_>) {
+        let shader = &self.post_process_shaders[index];
+        pass.set_pipeline(&shader.pipeline);
+        pass.set_bind_group(0, &shader.bind_group, &[]);
+        pass.draw(0..shader.vertex_count, 0..shader.instance_count);
+    }
+
+    pub(crate) fn glob_offset(&self, glob: &GpuValue) -> Option<u64> {
+        self.glob_offsets.get(glob).copied()
+    }
+
+    fn glob_offsets(app: &App) -> FxHashMap<GpuValue, u64> {
+        let buffer_type_details = GpuTypeDetails::from_fields(&app.globs, &app.types);
+        app.globs
+            .iter()
+            .enumerate()
+            .map(|(position, glob)| (*glob, buffer_type_details.field_offset(position)))
+            .collect()
+    }
+
+    fn create_buffer(app: &App, device: &Device) -> Result<Option<Buffer>, RagnaError> {
         if app.globs.is_empty() {
-            None
+            Ok(None)
         } else {
-            Some(device.create_buffer(&BufferDescriptor {
+            let size = GpuTypeDetails::from_fields(&app.globs, &app.types).size();
+            let limits = device.limits();
+            let max_size = limits
+                .max_buffer_size
+                .min(limits.max_storage_buffer_binding_size.into());
+            if size > max_size {
+                return Err(RagnaError::BufferTooLarge { size, max_size });
+            }
+            Ok(Some(device.create_buffer(&BufferDescriptor {
                 label: Some("ragna:buffer"),
-                size: GpuTypeDetails::from_fields(&app.globs, &app.types).size(),
-                usage: BufferUsages::STORAGE | BufferUsages::COPY_SRC,
+                size,
+                usage: BufferUsages::STORAGE | BufferUsages::COPY_SRC | BufferUsages::COPY_DST,
                 mapped_at_creation: false,
-            }))
+            })))
         }
     }
 
-    fn create_bind_group_entry(buffer: Option<&Buffer>) -> Option<BindGroupEntry<'_>> {
-        buffer.map(|buffer| BindGroupEntry {
+    fn create_textures(
+        app: &App,
+        device: &Device,
+        queue: &Queue,
+    ) -> (Vec<Texture>, Vec<(TextureView, Sampler)>) {
+        let raw_textures: Vec<Texture> = app
+            .textures
+            .iter()
+            .map(|(source, _)| match source {
+                TextureSource::Image(image) => Self::create_image_texture(device, queue, image),
+                TextureSource::OffscreenTarget { size } => {
+                    Self::create_offscreen_texture(device, *size)
+                }
+            })
+            .collect();
+        let textures = raw_textures
+            .iter()
+            .zip(&app.textures)
+            .map(|(texture, (_, sampler))| {
+                (
+                    texture.create_view(&TextureViewDescriptor::default()),
+                    Self::create_sampler(device, *sampler),
+                )
+            })
+            .collect();
+        (raw_textures, textures)
+    }
+
+    fn create_compute_shaders(
+        app: &App,
+        device: &Device,
+        init_shader_code: String,
+        buffer: Option<&Buffer>,
+        pixel_texture: Option<&TextureView>,
+        textures: &[(TextureView, Sampler)],
+    ) -> Result<(ComputeShader, Vec<ComputeShader>), RagnaError> {
+        let layout = Self::create_bind_group_layout(
+            device,
+            ShaderStages::COMPUTE,
+            buffer,
+            pixel_texture
+                .is_some()
+                .then_some(BindingType::StorageTexture {
+                    access: StorageTextureAccess::WriteOnly,
+                    format: PIXEL_TEXTURE_FORMAT,
+                    view_dimension: TextureViewDimension::D2,
+                }),
+            textures.len(),
+        );
+        let entries = Self::compute_bind_group_entries(buffer, pixel_texture, textures);
+        let bindings = Bindings {
+            layout: &layout,
+            entries: &entries,
+        };
+        Ok((
+            ComputeShader::new(init_shader_code, device, &bindings)?,
+            app.wgsl_update_shaders()
+                .map(|code| ComputeShader::new(code, device, &bindings))
+                .collect::<Result<_, _>>()?,
+        ))
+    }
+
+    fn create_pixel_shader(
+        app: &App,
+        device: &Device,
+        pixel_texture: &TextureView,
+        target_format: TextureFormat,
+        sample_count: u32,
+    ) -> Result<RenderShader, RagnaError> {
+        let draw = Draw::Pixels;
+        let code = draw.wgsl_code(&app.types, &app.globs);
+        let layout = device.create_bind_group_layout(&BindGroupLayoutDescriptor {
+            label: Some("ragna:pixel_bind_group_layout"),
+            entries: &[Self::create_texture_layout_entry(0, ShaderStages::FRAGMENT)],
+        });
+        let entries = [Self::create_texture_entry(0, pixel_texture)];
+        let bindings = Bindings {
+            layout: &layout,
+            entries: &entries,
+        };
+        RenderShader::new(
+            code,
+            &draw,
+            Some(DrawConfig::default()),
+            device,
+            &bindings,
+            target_format,
+            sample_count,
+        )
+    }
+
+    fn create_post_process_shaders(
+        app: &App,
+        device: &Device,
+        buffer: Option<&Buffer>,
+        textures: &[(TextureView, Sampler)],
+        post_process_textures: &[(Texture, TextureView)],
+        target_format: TextureFormat,
+    ) -> Result<Vec<RenderShader>, RagnaError> {
+        // the layout has one more texture than other shaders, so it is only created if needed
+        if post_process_textures.is_empty() {
+            return Ok(vec![]);
+        }
+        let layout = Self::create_bind_group_layout(
+            device,
+            ShaderStages::VERTEX_FRAGMENT,
+            buffer,
+            Some(Self::texture_binding_type()),
+            textures.len(),
+        );
+        app.wgsl_post_process_shaders()
+            .zip(post_process_textures)
+            .map(|((code, draw), (_, source_view))| {
+                let entries = Self::compute_bind_group_entries(buffer, Some(source_view), textures);
+                let bindings = Bindings {
+                    layout: &layout,
+                    entries: &entries,
+                };
+                // post-processes are applied on the resolved target
+                RenderShader::new(code, draw, None, device, &bindings, target_format, 1)
+            })
+            .collect()
+    }
+
+    fn check_texture_count(app: &App, device: &Device) -> Result<(), RagnaError> {
+        let limits = device.limits();
+        let texture_count = u32::try_from(app.textures.len()).unwrap_or(u32::MAX);
+        // post-process shaders also sample the source texture, without sampler
+        let sampled_texture_count = texture_count + u32::from(!app.post_processes.is_empty());
+        if sampled_texture_count > limits.max_sampled_textures_per_shader_stage {
+            Err(RagnaError::TooManyTextures {
+                count: sampled_texture_count,
+                max_count: limits.max_sampled_textures_per_shader_stage,
+            })
+        } else if texture_count > limits.max_samplers_per_shader_stage {
+            Err(RagnaError::TooManyTextures {
+                count: texture_count,
+                max_count: limits.max_samplers_per_shader_stage,
+            })
+        } else {
+            Ok(())
+        }
+    }
+
+    fn create_offscreen_targets(
+        app: &App,
+        device: &Device,
+        layout: &BindGroupLayout,
+        buffer: Option<&Buffer>,
+        raw_textures: &[Texture],
+        textures: &[(TextureView, Sampler)],
+    ) -> Result<Vec<OffscreenTargetPass>, RagnaError> {
+        // a texture cannot be sampled while rendered, so it is replaced by an empty texture
+        let placeholder_texture = Self::create_offscreen_texture(device, (1, 1))
+            .create_view(&TextureViewDescriptor::default());
+        app.offscreen_targets
+            .iter()
+            .enumerate()
+            .map(|(index, target)| {
+                let sampled_textures: Vec<_> = textures
+                    .iter()
+                    .enumerate()
+                    .map(|(texture_index, (view, sampler))| {
+                        if texture_index == target.texture_index {
+                            (placeholder_texture.clone(), sampler.clone())
+                        } else {
+                            (view.clone(), sampler.clone())
+                        }
+                    })
+                    .collect();
+                let entries = Self::compute_bind_group_entries(buffer, None, &sampled_textures);
+                let bindings = Bindings {
+                    layout,
+                    entries: &entries,
+                };
+                let texture = raw_textures[target.texture_index].clone();
+                Ok(OffscreenTargetPass {
+                    view: texture.create_view(&TextureViewDescriptor::default()),
+                    depth_buffer: Self::create_depth_buffer(device, target.size, 1),
+                    shaders: app
+                        .wgsl_offscreen_shaders(index)
+                        .map(|(code, (draw, config))| {
+                            RenderShader::new(
+                                code,
+                                draw,
+                                Some(*config),
+                                device,
+                                &bindings,
+                                IMAGE_TEXTURE_FORMAT,
+                                1,
+                            )
+                        })
+                        .collect::<Result<_, _>>()?,
+                    texture,
+                })
+            })
+            .collect()
+    }
+
+    fn create_pixel_texture(device: &Device, size: (u32, u32)) -> TextureView {
+        let texture = device.create_texture(&TextureDescriptor {
+            label: Some("ragna:pixel_texture"),
+            size: Extent3d {
+                width: size.0,
+                height: size.1,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: TextureDimension::D2,
+            format: PIXEL_TEXTURE_FORMAT,
+            usage: TextureUsages::STORAGE_BINDING | TextureUsages::TEXTURE_BINDING,
+            view_formats: &[],
+        });
+        texture.create_view(&TextureViewDescriptor::default())
+    }
+
+    fn create_post_process_texture(
+        device: &Device,
+        format: TextureFormat,
+        size: (u32, u32),
+    ) -> (Texture, TextureView) {
+        let texture = device.create_texture(&TextureDescriptor {
+            label: Some("ragna:post_process_texture"),
+            size: Extent3d {
+                width: size.0,
+                height: size.1,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: TextureDimension::D2,
+            format,
+            usage: TextureUsages::RENDER_ATTACHMENT
+                | TextureUsages::TEXTURE_BINDING
+                | TextureUsages::COPY_SRC,
+            view_formats: &[],
+        });
+        let view = texture.create_view(&TextureViewDescriptor::default());
+        (texture, view)
+    }
+
+    fn create_offscreen_texture(device: &Device, size: (u32, u32)) -> Texture {
+        device.create_texture(&TextureDescriptor {
+            label: Some("ragna:offscreen_texture"),
+            size: Extent3d {
+                width: size.0,
+                height: size.1,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: TextureDimension::D2,
+            format: IMAGE_TEXTURE_FORMAT,
+            usage: TextureUsages::RENDER_ATTACHMENT
+                | TextureUsages::TEXTURE_BINDING
+                | TextureUsages::COPY_SRC,
+            view_formats: &[],
+        })
+    }
+
+    pub(crate) fn is_sample_count_supported(
+        adapter: &Adapter,
+        device: &Device,
+        target_format: TextureFormat,
+        samples: u32,
+    ) -> bool {
+        let device_features = device.features();
+        [target_format, DEPTH_BUFFER_FORMAT]
+            .into_iter()
+            .all(|format| {
+                // adapter specific features are only enabled if requested by the device
+                let format_features = if device_features
+                    .contains(Features::TEXTURE_ADAPTER_SPECIFIC_FORMAT_FEATURES)
+                {
+                    adapter.get_texture_format_features(format)
+                } else {
+                    format.guaranteed_format_features(device_features)
+                };
+                format_features.flags.sample_count_supported(samples)
+            })
+    }
+
+    pub(crate) fn create_depth_buffer(
+        device: &Device,
+        size: (u32, u32),
+        samples: u32,
+    ) -> TextureView {
+        let texture = device.create_texture(&TextureDescriptor {
+            label: Some("ragna:depth_texture"),
+            size: Extent3d {
+                width: size.0,
+                height: size.1,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: samples,
+            dimension: TextureDimension::D2,
+            format: DEPTH_BUFFER_FORMAT,
+            usage: TextureUsages::RENDER_ATTACHMENT,
+            view_formats: &[],
+        });
+        texture.create_view(&TextureViewDescriptor::default())
+    }
+
+    fn create_image_texture(device: &Device, queue: &Queue, image: &Image) -> Texture {
+        device.create_texture_with_data(
+            queue,
+            &TextureDescriptor {
+                label: Some("ragna:image_texture"),
+                size: Extent3d {
+                    width: image.size.0,
+                    height: image.size.1,
+                    depth_or_array_layers: 1,
+                },
+                mip_level_count: 1,
+                sample_count: 1,
+                dimension: TextureDimension::D2,
+                format: IMAGE_TEXTURE_FORMAT,
+                usage: TextureUsages::TEXTURE_BINDING,
+                view_formats: &[],
+            },
+            TextureDataOrder::LayerMajor,
+            &image.buffer,
+        )
+    }
+
+    fn create_sampler(device: &Device, config: SamplerConfig) -> Sampler {
+        let address_mode = match config.wrap {
+            TextureWrap::Clamp => AddressMode::ClampToEdge,
+            TextureWrap::Repeat => AddressMode::Repeat,
+            TextureWrap::MirrorRepeat => AddressMode::MirrorRepeat,
+        };
+        let filter = match config.filter {
+            TextureFilter::Nearest => FilterMode::Nearest,
+            TextureFilter::Linear => FilterMode::Linear,
+        };
+        device.create_sampler(&SamplerDescriptor {
+            label: Some("ragna:sampler"),
+            address_mode_u: address_mode,
+            address_mode_v: address_mode,
+            address_mode_w: address_mode,
+            mag_filter: filter,
+            min_filter: filter,
+            ..SamplerDescriptor::default()
+        })
+    }
+
+    fn create_bind_group_layout(
+        device: &Device,
+        visibility: ShaderStages,
+        buffer: Option<&Buffer>,
+        // pixel texture for compute shaders, or source texture for post-process shaders
+        binding_1_type: Option<BindingType>,
+        texture_count: usize,
+    ) -> BindGroupLayout {
+        let buffer_entry = buffer.map(|_| BindGroupLayoutEntry {
+            binding: 0,
+            visibility,
+            ty: BindingType::Buffer {
+                // vertex shaders cannot access storage buffers in write mode
+                ty: BufferBindingType::Storage {
+                    read_only: visibility != ShaderStages::COMPUTE,
+                },
+                has_dynamic_offset: false,
+                min_binding_size: None,
+            },
+            count: None,
+        });
+        let binding_1_entry = binding_1_type.map(|ty| BindGroupLayoutEntry {
+            binding: 1,
+            visibility,
+            ty,
+            count: None,
+        });
+        let texture_entries = (0..texture_count).flat_map(|index| {
+            let binding = wgsl::texture_binding(index);
+            [
+                Self::create_texture_layout_entry(binding, visibility),
+                BindGroupLayoutEntry {
+                    binding: binding + 1,
+                    visibility,
+                    ty: BindingType::Sampler(SamplerBindingType::Filtering),
+                    count: None,
+                },
+            ]
+        });
+        let entries: Vec<_> = buffer_entry
+            .into_iter()
+            .chain(binding_1_entry)
+            .chain(texture_entries)
+            .collect();
+        device.create_bind_group_layout(&BindGroupLayoutDescriptor {
+            label: Some("ragna:bind_group_layout"),
+            entries: &entries,
+        })
+    }
+
+    fn create_texture_layout_entry(binding: u32, visibility: ShaderStages) -> BindGroupLayoutEntry {
+        BindGroupLayoutEntry {
+            binding,
+            visibility,
+            ty: Self::texture_binding_type(),
+            count: None,
+        }
+    }
+
+    fn texture_binding_type() -> BindingType {
+        BindingType::Texture {
+            sample_type: TextureSampleType::Float { filterable: true },
+            view_dimension: TextureViewDimension::D2,
+            multisampled: false,
+        }
+    }
+
+    fn compute_bind_group_entries<'a>(
+        buffer: Option<&'a Buffer>,
+        // pixel texture for compute shaders, or source texture for post-process shaders
+        binding_1_texture: Option<&'a TextureView>,
+        textures: &'a [(TextureView, Sampler)],
+    ) -> Vec<BindGroupEntry<'a>> {
+        let texture_entries = textures
+            .iter()
+            .enumerate()
+            .flat_map(|(index, (view, sampler))| {
+                let binding = wgsl::texture_binding(index);
+                [
+                    Self::create_texture_entry(binding, view),
+                    BindGroupEntry {
+                        binding: binding + 1,
+                        resource: BindingResource::Sampler(sampler),
+                    },
+                ]
+            });
+        buffer
+            .map(Self::create_buffer_entry)
+            .into_iter()
+            .chain(binding_1_texture.map(|view| Self::create_texture_entry(1, view)))
+            .chain(texture_entries)
+            .collect()
+    }
+
+    fn create_buffer_entry(buffer: &Buffer) -> BindGroupEntry<'_> {
+        BindGroupEntry {
             binding: 0,
             resource: buffer.as_entire_binding(),
+        }
+    }
+
+    fn create_texture_entry(binding: u32, view: &TextureView) -> BindGroupEntry<'_> {
+        BindGroupEntry {
+            binding,
+            resource: BindingResource::TextureView(view),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct OffscreenTargetPass {
+    pub(crate) texture: Texture,
+    pub(crate) view: TextureView,
+    pub(crate) depth_buffer: TextureView,
+    shaders: Vec<RenderShader>,
+}
+
+#[derive(Debug)]
+struct Bindings<'a> {
+    layout: &'a BindGroupLayout,
+    entries: &'a [BindGroupEntry<'a>],
+}
+
+impl Bindings<'_> {
+    fn create_pipeline_layout(&self, device: &Device) -> PipelineLayout {
+        device.create_pipeline_layout(&PipelineLayoutDescriptor {
+            label: Some("ragna:pipeline_layout"),
+            bind_group_layouts: &[self.layout],
+            push_constant_ranges: &[],
+        })
+    }
+
+    fn create_bind_group(&self, device: &Device) -> BindGroup {
+        device.create_bind_group(&BindGroupDescriptor {
+            label: Some("ragna:bind_group"),
+            layout: self.layout,
+            entries: self.entries,
         })
     }
 }
@@ -72,33 +755,171 @@ impl Program {
 #[derive(Debug)]
 struct ComputeShader {
     pipeline: ComputePipeline,
-    bind_group: Option<BindGroup>,
+    bind_group_layout: BindGroupLayout,
+    bind_group: BindGroup,
 }
 
 impl ComputeShader {
-    fn new(code: String, device: &Device, bind_group_entry: Option<BindGroupEntry<'_>>) -> Self {
+    fn new(code: String, device: &Device, bindings: &Bindings<'_>) -> Result<Self, RagnaError> {
+        device.push_error_scope(ErrorFilter::Validation);
         let module = device.create_shader_module(ShaderModuleDescriptor {
             label: Some("ragna:compute_shader:module"),
-            source: wgpu::ShaderSource::Wgsl(code.into()),
+            source: wgpu::ShaderSource::Wgsl(code.as_str().into()),
         });
         let pipeline = device.create_compute_pipeline(&ComputePipelineDescriptor {
             label: Some("ragna:compute_shader:pipeline"),
-            layout: None,
+            layout: Some(&bindings.create_pipeline_layout(device)),
             module: &module,
             entry_point: None,
             compilation_options: wgpu::PipelineCompilationOptions::default(),
             cache: None,
         });
-        let bind_group = bind_group_entry.map(|entry| {
-            device.create_bind_group(&wgpu::BindGroupDescriptor {
-                label: Some("ragna:compute_shader:bind_group"),
-                layout: &pipeline.get_bind_group_layout(0),
-                entries: &[entry],
-            })
+        let bind_group = bindings.create_bind_group(device);
+        if let Some(error) = executor::block_on(device.pop_error_scope()) {
+            return Err(validation_error(&code, &module, &error));
+        }
+        Ok(Self {
+            pipeline,
+            bind_group_layout: bindings.layout.clone(),
+            bind_group,
+        })
+    }
+
+    // coverage: off (window cannot be tested)
+    fn update_bind_group(&mut self, device: &Device, entries: &[BindGroupEntry<'_>]) {
+        let bindings = Bindings {
+            layout: &self.bind_group_layout,
+            entries,
+        };
+        self.bind_group = bindings.create_bind_group(device);
+    }
+    // coverage: on
+}
+
+#[derive(Debug)]
+struct RenderShader {
+    pipeline: RenderPipeline,
+    bind_group_layout: BindGroupLayout,
+    bind_group: BindGroup,
+    vertex_count: u32,
+    instance_count: u32,
+}
+
+impl RenderShader {
+    // `config` is `None` for post-processes, which replace the target without depth buffer
+    fn new(
+        code: String,
+        draw: &Draw,
+        config: Option<DrawConfig>,
+        device: &Device,
+        bindings: &Bindings<'_>,
+        target_format: TextureFormat,
+        sample_count: u32,
+    ) -> Result<Self, RagnaError> {
+        device.push_error_scope(ErrorFilter::Validation);
+        let module = device.create_shader_module(ShaderModuleDescriptor {
+            label: Some("ragna:render_shader:module"),
+            source: wgpu::ShaderSource::Wgsl(code.as_str().into()),
+        });
+        let pipeline = device.create_render_pipeline(&RenderPipelineDescriptor {
+            label: Some("ragna:render_shader:pipeline"),
+            layout: Some(&bindings.create_pipeline_layout(device)),
+            vertex: VertexState {
+                module: &module,
+                entry_point: Some("vs_main"),
+                compilation_options: wgpu::PipelineCompilationOptions::default(),
+                buffers: &[],
+            },
+            primitive: PrimitiveState {
+                topology: draw.topology(),
+                ..PrimitiveState::default()
+            },
+            depth_stencil: config.map(Self::depth_stencil_state),
+            multisample: MultisampleState {
+                count: sample_count,
+                ..MultisampleState::default()
+            },
+            fragment: Some(FragmentState {
+                module: &module,
+                entry_point: Some("fs_main"),
+                compilation_options: wgpu::PipelineCompilationOptions::default(),
+                targets: &[Some(ColorTargetState {
+                    format: target_format,
+                    blend: Some(
+                        config.map_or(BlendState::REPLACE, |config| config.blend.to_wgpu()),
+                    ),
+                    write_mask: ColorWrites::ALL,
+                })],
+            }),
+            multiview: None,
+            cache: None,
         });
-        Self {
+        let bind_group = bindings.create_bind_group(device);
+        if let Some(error) = executor::block_on(device.pop_error_scope()) {
+            return Err(validation_error(&code, &module, &error));
+        }
+        Ok(Self {
             pipeline,
+            bind_group_layout: bindings.layout.clone(),
             bind_group,
+            vertex_count: draw.vertex_count(),
+            instance_count: draw.instance_count(),
+        })
+    }
+
+    fn depth_stencil_state(config: DrawConfig) -> DepthStencilState {
+        DepthStencilState {
+            format: DEPTH_BUFFER_FORMAT,
+            depth_write_enabled: config.depth_write,
+            depth_compare: if config.depth_test {
+                CompareFunction::LessEqual
+            } else {
+                CompareFunction::Always
+            },
+            stencil: StencilState::default(),
+            bias: DepthBiasState::default(),
         }
     }
+
+    // coverage: off (window cannot be tested)
+    fn update_bind_group(&mut self, device: &Device, entries: &[BindGroupEntry<'_>]) {
+        let bindings = Bindings {
+            layout: &self.bind_group_layout,
+            entries,
+        };
+        self.bind_group = bindings.create_bind_group(device);
+    }
+    // coverage: on
+}
+
+fn validation_error(code: &str, module: &ShaderModule, error: &wgpu::Error) -> RagnaError {
+    let compilation_error = executor::block_on(module.get_compilation_info())
+        .messages
+        .into_iter()
+        .find(|message| message.message_type == CompilationMessageType::Error);
+    if let Some(compilation_error) = compilation_error {
+        RagnaError::ShaderValidation {
+            wgsl_excerpt: compilation_error.location.map_or_else(
+                || code.into(),
+                |location| wgsl_excerpt(code, location.line_number as usize),
+            ),
+            message: compilation_error.message,
+        }
+    } else {
+        RagnaError::ShaderValidation {
+            message: error.to_string(),
+            wgsl_excerpt: code.into(),
+        }
+    }
+}
+
+fn wgsl_excerpt(code: &str, line_number: usize) -> String {
+    let first_line_number = line_number.saturating_sub(WGSL_EXCERPT_LINE_MARGIN).max(1);
+    code.lines()
+        .enumerate()
+        .map(|(index, line)| (index + 1, line))
+        .skip(first_line_number - 1)
+        .take(line_number - first_line_number + WGSL_EXCERPT_LINE_MARGIN + 1)
+        .map(|(number, line)| format!("{number:>5} | {line}"))
+        .join("\n")
 }
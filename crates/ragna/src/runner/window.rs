@@ -1,19 +1,25 @@
+use crate::app::StepCallback;
 use crate::runner::common::{Runner, TargetSpecialized};
-use crate::App;
+use crate::{App, InputEvent, RagnaError, WindowContext};
+use derive_where::derive_where;
 use wgpu::Color;
 use winit::application::ApplicationHandler;
 use winit::dpi::PhysicalSize;
-use winit::event::WindowEvent;
+use winit::event::{ElementState, KeyEvent, MouseScrollDelta, WindowEvent};
 use winit::event_loop::ActiveEventLoop;
+use winit::keyboard::PhysicalKey;
 use winit::window::WindowId;
 
 // coverage: off (window cannot be tested)
 
-#[derive(Debug)]
+#[derive_where(Debug)]
 pub(crate) struct WindowRunner {
     app: App,
     background_color: Color,
     runner: Option<Runner>,
+    pub(crate) error: Option<RagnaError>,
+    #[derive_where(skip)]
+    step_callback: Option<StepCallback>,
 }
 
 impl ApplicationHandler for WindowRunner {
@@ -32,6 +38,34 @@ impl ApplicationHandler for WindowRunner {
             WindowEvent::RedrawRequested => self.update(),
             WindowEvent::CloseRequested => event_loop.exit(),
             WindowEvent::Resized(size) => self.update_window_size(size),
+            WindowEvent::KeyboardInput { event, .. } => {
+                if let Some(event) = Self::key_event(&event) {
+                    self.handle_input(event);
+                }
+            }
+            WindowEvent::MouseInput { state, button, .. } => {
+                self.handle_input(match state {
+                    ElementState::Pressed => InputEvent::MouseButtonPressed(button),
+                    ElementState::Released => InputEvent::MouseButtonReleased(button),
+                });
+            }
+            #[allow(clippy::cast_possible_truncation)]
+            WindowEvent::CursorMoved { position, .. } => {
+                self.handle_input(InputEvent::MouseMoved {
+                    x: position.x as f32,
+                    y: position.y as f32,
+                });
+            }
+            #[allow(clippy::cast_possible_truncation)]
+            WindowEvent::MouseWheel { delta, .. } => {
+                self.handle_input(match delta {
+                    MouseScrollDelta::LineDelta(x, y) => InputEvent::MouseWheelScrolled { x, y },
+                    MouseScrollDelta::PixelDelta(delta) => InputEvent::MouseWheelScrolled {
+                        x: delta.x as f32,
+                        y: delta.y as f32,
+                    },
+                });
+            }
             _ => (),
         }
     }
@@ -49,11 +83,17 @@ impl ApplicationHandler for WindowRunner {
 }
 
 impl WindowRunner {
-    pub(crate) fn new(app: App, background_color: Color) -> Self {
+    pub(crate) fn new(
+        app: App,
+        background_color: Color,
+        step_callback: Option<StepCallback>,
+    ) -> Self {
         Self {
             app,
             background_color,
             runner: None,
+            error: None,
+            step_callback,
         }
     }
 
@@ -61,17 +101,42 @@ impl WindowRunner {
         if let Some(runner) = &mut self.runner {
             runner.refresh_surface();
         } else {
-            self.runner = Some(Runner::new_window(
-                &self.app,
-                event_loop,
-                self.background_color,
-            ));
+            match Runner::new_window(&self.app, event_loop, self.background_color) {
+                Ok(runner) => self.runner = Some(runner),
+                Err(error) => {
+                    self.error = Some(error);
+                    event_loop.exit();
+                }
+            }
         }
     }
 
     fn update(&mut self) {
         if let Some(runner) = &mut self.runner {
             runner.run_step();
+            if let Some(step_callback) = &mut self.step_callback {
+                step_callback(&mut WindowContext {
+                    app: &self.app,
+                    runner,
+                });
+            }
+        }
+    }
+
+    fn handle_input(&mut self, event: InputEvent) {
+        if let Some(runner) = &mut self.runner {
+            runner.input.handle(event);
+        }
+    }
+
+    fn key_event(event: &KeyEvent) -> Option<InputEvent> {
+        if let PhysicalKey::Code(key) = event.physical_key {
+            match event.state {
+                ElementState::Pressed => Some(InputEvent::KeyPressed(key)),
+                ElementState::Released => Some(InputEvent::KeyReleased(key)),
+            }
+        } else {
+            None
         }
     }
 
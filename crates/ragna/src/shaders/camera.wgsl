@@ -0,0 +1,54 @@
+fn {{world_to_screen_fn}}(position: vec2f) -> vec2f {
+    let camera = {{camera}};
+    let offset = (position - camera.f0) * camera.f1;
+    let rotation_cos = cos(camera.f2);
+    let rotation_sin = sin(camera.f2);
+    return ragna_viewport_center() + vec2f(
+        offset.x * rotation_cos + offset.y * rotation_sin,
+        -offset.x * rotation_sin + offset.y * rotation_cos,
+    );
+}
+
+fn {{screen_to_world_fn}}(position: vec2f) -> vec2f {
+    let camera = {{camera}};
+    let offset = position - ragna_viewport_center();
+    let rotation_cos = cos(camera.f2);
+    let rotation_sin = sin(camera.f2);
+    let rotated_offset = vec2f(
+        offset.x * rotation_cos - offset.y * rotation_sin,
+        offset.x * rotation_sin + offset.y * rotation_cos,
+    );
+    return rotated_offset / camera.f1 + camera.f0;
+}
+
+fn {{screen_to_ndc_fn}}(position: vec2f) -> vec2f {
+    let target_size = vec2f({{target_size}});
+    return vec2f(position.x / target_size.x * 2. - 1., 1. - position.y / target_size.y * 2.);
+}
+
+fn {{ndc_to_screen_fn}}(position: vec2f) -> vec2f {
+    let target_size = vec2f({{target_size}});
+    return vec2f((position.x + 1.) / 2. * target_size.x, (1. - position.y) / 2. * target_size.y);
+}
+
+fn {{world_to_ndc_fn}}(position: vec2f) -> vec2f {
+    return {{screen_to_ndc_fn}}({{world_to_screen_fn}}(position));
+}
+
+fn {{ndc_to_world_fn}}(position: vec2f) -> vec2f {
+    return {{screen_to_world_fn}}({{ndc_to_screen_fn}}(position));
+}
+
+fn {{is_in_viewport_fn}}(position: vec2f) -> bool {
+    let camera = {{camera}};
+    let target_size = vec2f({{target_size}});
+    let viewport_position = camera.f3 * target_size;
+    let viewport_end = viewport_position + camera.f4 * target_size;
+    return all(position >= viewport_position) && all(position < viewport_end);
+}
+
+fn ragna_viewport_center() -> vec2f {
+    let camera = {{camera}};
+    return (camera.f3 + camera.f4 / 2.) * vec2f({{target_size}});
+}
+
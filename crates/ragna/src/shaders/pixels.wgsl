@@ -0,0 +1,13 @@
+@group(0) @binding(0)
+var pixels: texture_2d<f32>;
+
+@vertex
+fn vs_main(@builtin(vertex_index) vertex_index: u32) -> @builtin(position) vec4f {
+    let corner = vec2f(f32(vertex_index % 2u), f32(vertex_index / 2u));
+    return vec4f(corner.x * 2. - 1., 1. - corner.y * 2., 1., 1.);
+}
+
+@fragment
+fn fs_main(@builtin(position) position: vec4f) -> @location(0) vec4f {
+    return textureLoad(pixels, vec2u(position.xy), 0);
+}
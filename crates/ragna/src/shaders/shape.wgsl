@@ -0,0 +1,29 @@
+struct ShapeVertex {
+    @builtin(position) position: vec4f,
+    @location(0) color: vec4f,
+}
+
+@vertex
+fn vs_main(
+    @builtin(vertex_index) vertex_index: u32,
+    @builtin(instance_index) instance_index: u32,
+) -> ShapeVertex {
+    let shape = {{shapes}}[instance_index];
+    let corner = (vec2f(f32(vertex_index % 2u), f32(vertex_index / 2u)) - 0.5) * shape.f1;
+    let rotation_cos = cos(shape.f2);
+    let rotation_sin = sin(shape.f2);
+    let rotated_corner = vec2f(
+        corner.x * rotation_cos - corner.y * rotation_sin,
+        corner.x * rotation_sin + corner.y * rotation_cos,
+    );
+    let position = {{world_to_ndc_fn}}(shape.f0 + rotated_corner);
+    return ShapeVertex(vec4f(position, 1. - shape.f4, 1.), shape.f3);
+}
+
+@fragment
+fn fs_main(vertex: ShapeVertex) -> @location(0) vec4f {
+    if !{{is_in_viewport_fn}}(vertex.position.xy) {
+        discard;
+    }
+    return vertex.color;
+}
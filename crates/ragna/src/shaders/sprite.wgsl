@@ -0,0 +1,36 @@
+struct SpriteVertex {
+    @builtin(position) position: vec4f,
+    @location(0) uv: vec2f,
+    @location(1) @interpolate(flat) texture: u32,
+    @location(2) tint: vec4f,
+}
+
+@vertex
+fn vs_main(
+    @builtin(vertex_index) vertex_index: u32,
+    @builtin(instance_index) instance_index: u32,
+) -> SpriteVertex {
+    let sprite = {{sprites}}[instance_index];
+    let relative_corner = vec2f(f32(vertex_index % 2u), f32(vertex_index / 2u));
+    let corner = (relative_corner - 0.5) * sprite.f1;
+    let rotation_cos = cos(sprite.f2);
+    let rotation_sin = sin(sprite.f2);
+    let rotated_corner = vec2f(
+        corner.x * rotation_cos - corner.y * rotation_sin,
+        corner.x * rotation_sin + corner.y * rotation_cos,
+    );
+    let position = {{world_to_ndc_fn}}(sprite.f0 + rotated_corner);
+    let flipped_corner = select(relative_corner, 1. - relative_corner, vec2(sprite.f7 != 0u, sprite.f8 != 0u));
+    let texture_size = vec2f({{texture_size_fn}}(sprite.f3.f0));
+    let uv = (sprite.f4 + flipped_corner * sprite.f5) / texture_size;
+    return SpriteVertex(vec4f(position, 1. - sprite.f9, 1.), uv, sprite.f3.f0, sprite.f6);
+}
+
+@fragment
+fn fs_main(vertex: SpriteVertex) -> @location(0) vec4f {
+    let color = {{sample_fn}}(vertex.texture, vertex.uv) * vertex.tint;
+    if color.a <= 0. || !{{is_in_viewport_fn}}(vertex.position.xy) {
+        discard;
+    }
+    return color;
+}
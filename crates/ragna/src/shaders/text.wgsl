@@ -0,0 +1,38 @@
+struct TextVertex {
+    @builtin(position) position: vec4f,
+    @location(0) uv: vec2f,
+    @location(1) @interpolate(flat) texture: u32,
+    @location(2) color: vec4f,
+}
+
+@vertex
+fn vs_main(
+    @builtin(vertex_index) vertex_index: u32,
+    @builtin(instance_index) instance_index: u32,
+) -> TextVertex {
+    let font = {{text}}.f3;
+    let texture_size = vec2f({{texture_size_fn}}(font.f0.f0));
+    let row_count = u32(texture_size.y / font.f1.y);
+    let glyph = {{text}}.f4[instance_index] - font.f3;
+    let is_displayed = instance_index < {{text}}.f5
+        && {{text}}.f4[instance_index] >= font.f3
+        && glyph / font.f2 < row_count;
+    if !is_displayed {
+        return TextVertex(vec4f(), vec2f(), 0u, vec4f());
+    }
+    let relative_corner = vec2f(f32(vertex_index % 2u), f32(vertex_index / 2u));
+    let pixel = {{text}}.f0 + (vec2f(f32(instance_index), 0.) + relative_corner) * {{text}}.f1;
+    let position = {{screen_to_ndc_fn}}(pixel);
+    let glyph_position = vec2f(f32(glyph % font.f2), f32(glyph / font.f2)) * font.f1;
+    let uv = (glyph_position + relative_corner * font.f1) / texture_size;
+    return TextVertex(vec4f(position, 1. - {{text}}.f6, 1.), uv, font.f0.f0, {{text}}.f2);
+}
+
+@fragment
+fn fs_main(vertex: TextVertex) -> @location(0) vec4f {
+    let color = {{sample_fn}}(vertex.texture, vertex.uv) * vertex.color;
+    if color.a <= 0. {
+        discard;
+    }
+    return color;
+}
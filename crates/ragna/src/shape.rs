@@ -0,0 +1,31 @@
+pub use gpu::{Shape, ShapeCpu};
+
+#[crate::gpu]
+mod gpu {
+    use crate::{F32x2, F32x4, F32};
+
+    /// A rectangle drawn at each step.
+    ///
+    /// Shapes are drawn with [`App::with_shapes`](crate::App::with_shapes).
+    pub struct Shape {
+        /// The position in world coordinates of the shape center.
+        ///
+        /// World coordinates are transformed by the [`camera`](crate::camera()). With the default
+        /// camera, they correspond to pixels with the origin at the top-left corner of the target.
+        pub position: F32x2,
+        /// The width and height in world coordinates.
+        pub size: F32x2,
+        /// The clockwise rotation in radians around the shape center.
+        pub rotation: F32,
+        /// The RGBA components between `0.0` and `1.0`.
+        pub color: F32x4,
+        /// The depth between `0.0` and `1.0`.
+        ///
+        /// A shape with a greater value is displayed in front of a shape with a lower value.
+        /// Shapes with the same depth are displayed in drawing order.
+        ///
+        /// By default, a translucent shape hides shapes drawn later behind it instead of being
+        /// blended with them, see [`DrawConfig::depth_write`](crate::DrawConfig::depth_write).
+        pub z: F32,
+    }
+}
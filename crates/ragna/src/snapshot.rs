@@ -0,0 +1,72 @@
+use crate::{Cpu, Gpu, GpuTypeDetails, GpuValue};
+
+/// A trait implemented for tuples of GPU values that can be read together.
+///
+/// See [`TextureApp::read_many`](crate::TextureApp::read_many).
+pub trait GpuTuple {
+    /// The tuple of read CPU values.
+    type Cpu;
+
+    #[doc(hidden)]
+    fn values(&self) -> Vec<GpuValue>;
+
+    #[doc(hidden)]
+    fn from_gpu(bytes: &[Vec<u8>]) -> Self::Cpu;
+}
+
+macro_rules! gpu_tuple {
+    ($($type_:ident: $index:tt),+) => {
+        impl<$($type_: Gpu),+> GpuTuple for ($($type_,)+) {
+            type Cpu = ($(Option<$type_::Cpu>,)+);
+
+            fn values(&self) -> Vec<GpuValue> {
+                vec![$(self.$index.value()),+]
+            }
+
+            fn from_gpu(bytes: &[Vec<u8>]) -> Self::Cpu {
+                ($(read_value::<$type_>(&bytes[$index]),)+)
+            }
+        }
+    };
+}
+
+gpu_tuple!(A: 0);
+gpu_tuple!(A: 0, B: 1);
+gpu_tuple!(A: 0, B: 1, C: 2);
+gpu_tuple!(A: 0, B: 1, C: 2, D: 3);
+gpu_tuple!(A: 0, B: 1, C: 2, D: 3, E: 4);
+gpu_tuple!(A: 0, B: 1, C: 2, D: 3, E: 4, F: 5);
+gpu_tuple!(A: 0, B: 1, C: 2, D: 3, E: 4, F: 5, G: 6);
+gpu_tuple!(A: 0, B: 1, C: 2, D: 3, E: 4, F: 5, G: 6, H: 7);
+
+/// A copy of all global variables stored on GPU side.
+///
+/// The snapshot is retrieved with [`TextureApp::read_all`](crate::TextureApp::read_all).
+#[derive(Debug, Clone)]
+pub struct GlobSnapshot {
+    pub(crate) bytes: Vec<u8>,
+    pub(crate) globs: Vec<GpuValue>,
+    pub(crate) details: GpuTypeDetails,
+}
+
+impl GlobSnapshot {
+    /// Reads a value stored in the snapshot.
+    ///
+    /// If the passed value is not a global variable registered in the application,
+    /// `None` is returned.
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn read<T: Gpu>(&self, value: T) -> Option<T::Cpu> {
+        let position = self.globs.iter().position(|glob| glob == &value.value())?;
+        let offset = self.details.field_offset(position) as usize;
+        let size = T::details().size() as usize;
+        Some(Cpu::from_gpu(self.bytes.get(offset..offset + size)?))
+    }
+}
+
+pub(crate) fn read_value<T: Gpu>(bytes: &[u8]) -> Option<T::Cpu> {
+    if bytes.is_empty() {
+        None
+    } else {
+        Some(Cpu::from_gpu(bytes))
+    }
+}
@@ -0,0 +1,43 @@
+pub use gpu::{Sprite, SpriteCpu};
+
+#[crate::gpu]
+mod gpu {
+    use crate::{Bool, F32x2, F32x4, Texture, F32};
+
+    /// A textured rectangle drawn at each step.
+    ///
+    /// Sprites are drawn with [`App::with_sprites`](crate::App::with_sprites).
+    pub struct Sprite {
+        /// The position in world coordinates of the sprite center.
+        ///
+        /// World coordinates are transformed by the [`camera`](crate::camera()). With the default
+        /// camera, they correspond to pixels with the origin at the top-left corner of the target.
+        pub position: F32x2,
+        /// The width and height in world coordinates.
+        pub size: F32x2,
+        /// The clockwise rotation in radians around the sprite center.
+        pub rotation: F32,
+        /// The texture containing the sprite image, e.g. an atlas.
+        pub texture: Texture,
+        /// The position in pixels of the top-left corner of the texture region to display.
+        pub region_position: F32x2,
+        /// The width and height in pixels of the texture region to display.
+        pub region_size: F32x2,
+        /// The RGBA components between `0.0` and `1.0` multiplied with the texture color.
+        pub tint: F32x4,
+        /// Whether the texture region is flipped horizontally.
+        pub flip_x: Bool,
+        /// Whether the texture region is flipped vertically.
+        pub flip_y: Bool,
+        /// The depth between `0.0` and `1.0`.
+        ///
+        /// A sprite with a greater value is displayed in front of a sprite with a lower value.
+        /// Sprites with the same depth are displayed in drawing order.
+        ///
+        /// By default, a translucent sprite hides sprites drawn later behind it instead of being
+        /// blended with them, see [`DrawConfig::depth_write`](crate::DrawConfig::depth_write).
+        ///
+        /// Fully transparent pixels are ignored, so they don't hide sprites displayed behind.
+        pub z: F32,
+    }
+}
@@ -0,0 +1,45 @@
+use std::time::Duration;
+
+/// Statistics about the steps run by an application.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[non_exhaustive]
+pub struct StepStats {
+    /// The number of run steps.
+    pub step_count: u64,
+    /// The duration of the last step.
+    pub last_duration: Duration,
+    /// The average duration of the steps.
+    pub average_duration: Duration,
+    /// The duration of the fastest step.
+    pub min_duration: Duration,
+    /// The duration of the slowest step.
+    pub max_duration: Duration,
+    total_duration: Duration,
+}
+
+impl StepStats {
+    pub(crate) fn register_step(&mut self, duration: Duration) {
+        self.min_duration = if self.step_count == 0 {
+            duration
+        } else {
+            self.min_duration.min(duration)
+        };
+        self.max_duration = self.max_duration.max(duration);
+        self.last_duration = duration;
+        self.total_duration += duration;
+        self.step_count += 1;
+        self.average_duration = Duration::from_nanos(
+            u64::try_from(
+                self.total_duration
+                    .as_nanos()
+                    .div_euclid(self.step_count.into()),
+            )
+            .unwrap_or(u64::MAX),
+        );
+        log::debug!(
+            "Step duration: {}µs ({}fps)",
+            duration.as_micros(),
+            (1. / duration.as_secs_f32()).round()
+        );
+    }
+}
@@ -0,0 +1,53 @@
+use crate::context::GpuContext;
+use crate::glob::builtin_globs;
+use crate::operations::{Operation, SetPixelOperation};
+use crate::types::gpu_bytes;
+use crate::{u32x2, F32x4, Gpu, GpuValue, U32x2, F32};
+
+builtin_globs! {
+    pub(crate) static TARGET_SIZE: U32x2 = "ragna::target_size";
+    pub(crate) static SCALE_FACTOR: F32 = "ragna::scale_factor";
+}
+
+/// Returns the width and height in pixels of the target.
+pub fn target_size() -> U32x2 {
+    crate::create_var(*TARGET_SIZE)
+}
+
+/// Returns the scale factor of the target.
+///
+/// This corresponds to the DPI scale factor of the window, or `1.0` for a texture target.
+pub fn scale_factor() -> F32 {
+    crate::create_var(*SCALE_FACTOR)
+}
+
+/// Sets the color of a pixel of the target.
+///
+/// `position` is the pixel position, with the origin at the top-left corner of the target.
+/// `color` corresponds to RGBA components between `0.0` and `1.0`.
+///
+/// Set pixels are displayed behind all draws and keep their color between steps.
+/// Pixels outside the target are ignored.
+///
+/// This function can only be called in `#[compute]` functions.
+pub fn set_pixel(position: U32x2, color: F32x4) {
+    GpuContext::run_current(|ctx| {
+        ctx.operations.push(Operation::SetPixel(SetPixelOperation {
+            position: position.value(),
+            color: color.value(),
+        }));
+    });
+}
+
+pub(crate) fn glob_bytes(size: (u32, u32), scale_factor: f32) -> [(GpuValue, Vec<u8>); 2] {
+    [
+        (
+            TARGET_SIZE.value(),
+            gpu_bytes(&u32x2 {
+                x: size.0,
+                y: size.1,
+            }),
+        ),
+        (SCALE_FACTOR.value(), gpu_bytes(&scale_factor)),
+    ]
+}
@@ -5,6 +5,8 @@ use std::{env, fs};
 
 /// Asserts a texture is the same as the expected texture.
 ///
+/// The actual texture is first converted with [`TextureData::to_rgba8`].
+///
 /// If the expected texture is not yet generated, it is saved in
 /// `$CARGO_MANIFEST_DIR/tests/expected/{key}.png` and the function panics. At the next function
 /// run, the function shouldn't panic if actual texture have not changed.
@@ -39,6 +41,7 @@ pub fn assert_same_texture(key: &str, actual: &TextureData) {
         .expect("`CARGO_MANIFEST_DIR` environment variable not set")
         + "/tests/expected";
     let expected_file: PathBuf = format!("{expected_folder}/{key}.png").into();
+    let actual = &actual.to_rgba8();
     if expected_file.exists() {
         let image = image::open(&expected_file).expect("cannot read expected texture from disk");
         assert_eq!(image.width(), actual.size.0, "texture width is different");
@@ -55,7 +58,10 @@ pub fn assert_same_texture(key: &str, actual: &TextureData) {
                 ColorType::Rgba8,
             )
             .expect("cannot save texture diff");
-            panic!("texture is different (diff saved in {diff_file:?})")
+            panic!(
+                "texture is different (diff saved in {})",
+                diff_file.display()
+            )
         }
     } else {
         fs::create_dir_all(expected_folder).expect("cannot create folder for expected texture");
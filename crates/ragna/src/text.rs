@@ -0,0 +1,110 @@
+use crate::{Cpu, U32};
+
+pub use gpu::{Font, FontCpu, Text, TextCpu};
+
+impl<const N: usize> Text<N> {
+    /// Removes all characters.
+    pub fn clear(&self) {
+        gpu::clear(self);
+    }
+
+    /// Appends a character from its code.
+    ///
+    /// The character is ignored if the text capacity is reached.
+    pub fn push_char(&self, char: U32) {
+        gpu::push_char(self, char);
+    }
+
+    /// Appends the characters of a string.
+    ///
+    /// Characters that don't fit in the text capacity are ignored.
+    pub fn push_str(&self, string: &str) {
+        for char in string.chars() {
+            self.push_char(u32::from(char).to_gpu());
+        }
+    }
+
+    /// Appends the decimal representation of a number.
+    ///
+    /// Digits that don't fit in the text capacity are ignored.
+    pub fn push_number(&self, number: U32) {
+        gpu::push_number(self, number);
+    }
+}
+
+#[allow(clippy::cast_possible_truncation)]
+fn capacity<const N: usize>() -> U32 {
+    (N as u32).to_gpu()
+}
+
+#[crate::gpu]
+mod gpu {
+    use crate::{Array, F32x2, F32x4, Texture, F32, U32};
+
+    /// A bitmap font stored in a texture atlas.
+    ///
+    /// The atlas contains glyphs of the same size, arranged in rows from the top-left corner and
+    /// ordered by character code.
+    pub struct Font {
+        /// The texture atlas containing the glyphs.
+        pub texture: Texture,
+        /// The width and height in pixels of a glyph in the atlas.
+        pub glyph_size: F32x2,
+        /// The number of glyphs in an atlas row.
+        pub columns: U32,
+        /// The character code of the first glyph in the atlas.
+        pub first_char: U32,
+    }
+
+    /// A single-line text with a capacity of `N` characters drawn at each step.
+    ///
+    /// Texts are drawn with [`App::with_text`](crate::App::with_text).
+    ///
+    /// Texts are drawn in screen coordinates, so they are neither affected by the
+    /// [`camera`](crate::camera()) nor limited to its viewport.
+    pub struct Text<const N: usize> {
+        /// The position in pixels of the top-left corner of the text.
+        ///
+        /// The origin is the top-left corner of the target.
+        pub position: F32x2,
+        /// The width and height in pixels of a displayed character.
+        pub char_size: F32x2,
+        /// The RGBA components between `0.0` and `1.0` multiplied with the glyph color.
+        pub color: F32x4,
+        /// The font used to display the characters.
+        pub font: Font,
+        /// The character codes.
+        ///
+        /// Only the first `length` characters are displayed. Characters without a glyph in the
+        /// font atlas are not displayed.
+        pub chars: Array<U32, N>,
+        /// The number of displayed characters.
+        pub length: U32,
+        /// The depth between `0.0` and `1.0`.
+        ///
+        /// A text with a greater value is displayed in front of a text with a lower value.
+        pub z: F32,
+    }
+
+    pub(super) fn clear<const N: usize>(text: &Text<N>) {
+        text.length = 0u;
+    }
+
+    pub(super) fn push_char<const N: usize>(text: &Text<N>, char: U32) {
+        if text.length < super::capacity::<N>() {
+            text.chars[text.length] = char;
+            text.length += 1u;
+        }
+    }
+
+    pub(super) fn push_number<const N: usize>(text: &Text<N>, number: U32) {
+        let divisor = 1u;
+        while number / divisor >= 10u {
+            divisor *= 10u;
+        }
+        while divisor > 0u {
+            push_char(text, 48u + number / divisor % 10u);
+            divisor /= 10u;
+        }
+    }
+}
@@ -0,0 +1,143 @@
+use crate::{Cpu, F32x2, F32x4, Gpu, RagnaError, U32x2};
+use std::path::Path;
+
+pub use gpu::{Texture, TextureCpu};
+
+/// An RGBA image that can be loaded in a GPU texture.
+///
+/// Images are registered with [`App::with_texture`](crate::App::with_texture).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Image {
+    pub(crate) buffer: Vec<u8>,
+    pub(crate) size: (u32, u32),
+}
+
+impl Image {
+    /// Loads an image from a file.
+    ///
+    /// The image format is deduced from the file extension.
+    ///
+    /// # Errors
+    ///
+    /// An error is returned if the file cannot be read or decoded.
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, RagnaError> {
+        let path = path.as_ref();
+        let image = image::open(path).map_err(|error| RagnaError::ImageLoading {
+            path: path.display().to_string(),
+            message: error.to_string(),
+        })?;
+        let image = image.to_rgba8();
+        Ok(Self {
+            size: image.dimensions(),
+            buffer: image.into_raw(),
+        })
+    }
+
+    /// Creates an image from RGBA pixels.
+    ///
+    /// `buffer` contains the four components of each pixel, row by row from the top-left corner.
+    /// `size` corresponds to the width and height of the image.
+    ///
+    /// # Panics
+    ///
+    /// This will panic if `buffer` length doesn't correspond to `size`, or if the width or the
+    /// height is zero.
+    pub fn from_rgba(buffer: Vec<u8>, size: (u32, u32)) -> Self {
+        assert!(size.0 > 0 && size.1 > 0, "image size must not be zero");
+        assert_eq!(
+            buffer.len() as u64,
+            u64::from(size.0) * u64::from(size.1) * 4,
+            "image buffer length doesn't correspond to image size"
+        );
+        Self { buffer, size }
+    }
+
+    /// Returns the width and height of the image.
+    pub fn size(&self) -> (u32, u32) {
+        self.size
+    }
+}
+
+#[derive(Debug)]
+pub(crate) enum TextureSource {
+    Image(Image),
+    OffscreenTarget { size: (u32, u32) },
+}
+
+/// The configuration used to sample a texture.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct SamplerConfig {
+    /// The filter applied when the texture is magnified or minified.
+    pub filter: TextureFilter,
+    /// The behavior when texture coordinates are outside the texture.
+    pub wrap: TextureWrap,
+}
+
+/// The filter applied when sampling a texture.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[non_exhaustive]
+pub enum TextureFilter {
+    /// The nearest pixel is used.
+    #[default]
+    Nearest,
+    /// The nearest pixels are linearly interpolated.
+    Linear,
+}
+
+/// The behavior when sampling a texture outside its boundaries.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[non_exhaustive]
+pub enum TextureWrap {
+    /// The coordinates are clamped to the texture edges.
+    #[default]
+    Clamp,
+    /// The texture is repeated.
+    Repeat,
+    /// The texture is repeated and mirrored every other repetition.
+    MirrorRepeat,
+}
+
+impl Texture {
+    /// Returns a handle that doesn't refer to any texture.
+    ///
+    /// Sampling this handle returns a transparent black color.
+    pub fn none() -> Self {
+        TextureCpu { index: u32::MAX }.to_gpu()
+    }
+}
+
+/// Samples a texture at given coordinates.
+///
+/// `uv` corresponds to the normalized coordinates, where `(0.0, 0.0)` is the top-left corner of
+/// the texture and `(1.0, 1.0)` is the bottom-right corner.
+///
+/// The returned value corresponds to RGBA components between `0.0` and `1.0`.
+pub fn sample(texture: Texture, uv: F32x2) -> F32x4 {
+    crate::call_fn(
+        crate::wgsl::SAMPLE_FN_NAME,
+        vec![texture.index.value(), uv.value()],
+        false,
+    )
+}
+
+/// Returns the width and height in pixels of a texture.
+///
+/// If the handle doesn't refer to any texture, zero is returned for both dimensions.
+pub fn texture_size(texture: Texture) -> U32x2 {
+    crate::call_fn(
+        crate::wgsl::TEXTURE_SIZE_FN_NAME,
+        vec![texture.index.value()],
+        false,
+    )
+}
+
+#[crate::gpu]
+mod gpu {
+    use crate::U32;
+
+    /// A handle referring to a texture registered with
+    /// [`App::with_texture`](crate::App::with_texture).
+    pub struct Texture {
+        pub(crate) index: U32,
+    }
+}
@@ -0,0 +1,62 @@
+use crate::glob::builtin_globs;
+use crate::types::gpu_bytes;
+use crate::{Gpu, GpuValue, F32, U32};
+use std::time::{Duration, Instant};
+
+builtin_globs! {
+    pub(crate) static DELTA_SECONDS: F32 = "ragna::delta_seconds";
+    pub(crate) static ELAPSED_SECONDS: F32 = "ragna::elapsed_seconds";
+    pub(crate) static STEP_INDEX: U32 = "ragna::step_index";
+}
+
+/// Returns the duration in seconds between the start of the previous step and the current one.
+///
+/// The duration is zero during the first step, except if a fixed delta is configured
+/// (e.g. with [`TextureApp::with_fixed_delta`](crate::TextureApp::with_fixed_delta)).
+pub fn delta_seconds() -> F32 {
+    crate::create_var(*DELTA_SECONDS)
+}
+
+/// Returns the duration in seconds between the start of the first step and the current one.
+pub fn elapsed_seconds() -> F32 {
+    crate::create_var(*ELAPSED_SECONDS)
+}
+
+/// Returns the index of the current step, starting from zero.
+pub fn step_index() -> U32 {
+    crate::create_var(*STEP_INDEX)
+}
+
+#[derive(Debug, Default)]
+pub(crate) struct StepTime {
+    pub(crate) fixed_delta: Option<Duration>,
+    last_step_start: Option<Instant>,
+    delta: Duration,
+    elapsed: Duration,
+    step_index: u32,
+}
+
+impl StepTime {
+    pub(crate) fn start_step(&mut self) {
+        let now = Instant::now();
+        if let Some(last_step_start) = self.last_step_start {
+            self.delta = self.fixed_delta.unwrap_or(now - last_step_start);
+            self.elapsed += self.delta;
+            self.step_index = self.step_index.wrapping_add(1);
+        } else {
+            self.delta = self.fixed_delta.unwrap_or(Duration::ZERO);
+        }
+        self.last_step_start = Some(now);
+    }
+
+    pub(crate) fn glob_bytes(&self) -> [(GpuValue, Vec<u8>); 3] {
+        [
+            (DELTA_SECONDS.value(), gpu_bytes(&self.delta.as_secs_f32())),
+            (
+                ELAPSED_SECONDS.value(),
+                gpu_bytes(&self.elapsed.as_secs_f32()),
+            ),
+            (STEP_INDEX.value(), gpu_bytes(&self.step_index)),
+        ]
+    }
+}
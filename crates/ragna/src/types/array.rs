@@ -1,3 +1,4 @@
+use crate::operations::ARRAY_FN_NAME;
 use crate::{
     context, Cpu, Gpu, GpuTypeDetails, GpuValue, GreaterThan, Iterable, Wgsl, WgslConstructor, U32,
 };
@@ -15,7 +16,37 @@ pub struct Array<T, const N: usize> {
 impl<T: Gpu, const N: usize> Array<T, N> {
     /// Creates a new array.
     pub fn new(items: [T; N]) -> Self {
-        crate::call_fn("array", items.into_iter().map(Gpu::value).collect(), false)
+        crate::call_fn(
+            ARRAY_FN_NAME,
+            items.into_iter().map(Gpu::value).collect(),
+            false,
+        )
+    }
+
+    pub(crate) fn item_stride() -> u64 {
+        Self::stride(&T::details())
+    }
+
+    fn stride(item_details: &GpuTypeDetails) -> u64 {
+        GpuTypeDetails::round_up(item_details.alignment(), item_details.size())
+    }
+
+    #[allow(clippy::cast_possible_truncation)]
+    pub(crate) fn items_from_gpu(bytes: &[u8]) -> Vec<T::Cpu> {
+        bytes
+            .chunks(Self::item_stride() as usize)
+            .map(Cpu::from_gpu)
+            .collect()
+    }
+
+    #[allow(clippy::cast_possible_truncation)]
+    pub(crate) fn items_to_gpu(items: &[T::Cpu]) -> Vec<u8> {
+        let stride = Self::item_stride() as usize;
+        let mut bytes = vec![0; items.len() * stride];
+        for (item, item_bytes) in items.iter().zip(bytes.chunks_mut(stride)) {
+            item.write_gpu(item_bytes);
+        }
+        bytes
     }
 
     /// Creates a new array from a repeated item.
@@ -43,10 +74,8 @@ impl<T: Gpu, const N: usize> Gpu for Array<T, N> {
         GpuTypeDetails {
             type_id: TypeId::of::<Self>(),
             name: Some("array"),
-            array_generics: Some((item_details.clone().into(), N)),
-            size: Some(
-                N as u64 * GpuTypeDetails::round_up(item_details.alignment(), item_details.size()),
-            ),
+            array_length: Some(N),
+            size: Some(N as u64 * Self::stride(&item_details)),
             alignment: Some(item_details.alignment()),
             field_types: vec![item_details],
         }
@@ -78,6 +107,12 @@ impl<T: Cpu, const N: usize> Cpu for [T; N] {
             .expect("internal error: invalid GPU array")
     }
 
+    fn write_gpu(&self, bytes: &mut [u8]) {
+        for (item, item_bytes) in self.iter().zip(bytes.chunks_mut(bytes.len().div_euclid(N))) {
+            item.write_gpu(item_bytes);
+        }
+    }
+
     fn to_wgsl(&self) -> Wgsl {
         Wgsl::Constructor(WgslConstructor {
             type_id: TypeId::of::<Self::Gpu>(),
@@ -1,17 +1,19 @@
 use crate::context::GpuContext;
-use crate::operations::{ConstantAssignVarOperation, Operation};
+use crate::operations::{ConstantAssignVarOperation, ConstantValue, Operation};
 use crate::{Bool, Equal, U32};
 use derive_where::derive_where;
-use fxhash::FxHashMap;
+use fxhash::{FxHashMap, FxHashSet};
 use std::any::TypeId;
+use std::cell::RefCell;
+use std::hash::{Hash, Hasher};
+use std::ptr;
+use std::sync::{LazyLock, Mutex};
 
 pub(crate) mod array;
 pub(crate) mod primitive;
 pub(crate) mod range;
 pub(crate) mod vectors;
 
-const MAX_NESTED_FIELDS: usize = 15;
-
 /// A trait implemented for Rust types that have a corresponding CPU type.
 pub trait Cpu: Sized {
     /// The GPU type.
@@ -20,10 +22,17 @@ pub trait Cpu: Sized {
     #[doc(hidden)]
     fn from_gpu(bytes: &[u8]) -> Self;
 
+    #[doc(hidden)]
+    fn write_gpu(&self, bytes: &mut [u8]) {
+        write_wgsl_bytes(&self.to_wgsl(), &Self::Gpu::details(), bytes);
+    }
+
     #[doc(hidden)]
     fn to_wgsl(&self) -> Wgsl;
 
     /// Converts a value to a GPU value.
+    ///
+    /// Large values are uploaded to the GPU at startup instead of being embedded in shaders.
     fn to_gpu(&self) -> Self::Gpu {
         let var = crate::create_uninit_var::<Self::Gpu>();
         let left_value = var.value();
@@ -31,13 +40,52 @@ pub trait Cpu: Sized {
             ctx.operations
                 .push(Operation::ConstantAssignVar(ConstantAssignVarOperation {
                     left_value,
-                    right_value: self.to_wgsl(),
+                    right_value: ConstantValue::Cpu {
+                        bytes: gpu_bytes(self),
+                        to_wgsl: |bytes| Self::from_gpu(bytes).to_wgsl(),
+                    },
                 }));
         });
         var
     }
 }
 
+#[allow(clippy::cast_possible_truncation)]
+pub(crate) fn gpu_bytes<T: Cpu>(value: &T) -> Vec<u8> {
+    let mut bytes = vec![0; T::Gpu::details().size() as usize];
+    value.write_gpu(&mut bytes);
+    bytes
+}
+
+// slower conversion used when a CPU type doesn't write its bytes directly
+#[allow(clippy::cast_possible_truncation)]
+fn write_wgsl_bytes(wgsl: &Wgsl, details: &GpuTypeDetails, bytes: &mut [u8]) {
+    match wgsl {
+        Wgsl::Value(value) => {
+            let value_bytes = match details.name {
+                Some("i32") => value.parse().ok().map(i32::to_ne_bytes),
+                Some("u32") => value.parse().ok().map(u32::to_ne_bytes),
+                Some("f32") => value.parse().ok().map(f32::to_ne_bytes),
+                _ => None,
+            };
+            bytes[..4].copy_from_slice(&value_bytes.expect("unsupported WGSL value"));
+        }
+        Wgsl::Constructor(constructor) => {
+            for (index, arg) in constructor.args.iter().enumerate() {
+                let (offset, arg_details) = if details.is_struct() {
+                    (details.field_offset(index), &details.field_types[index])
+                } else {
+                    let item_details = &details.field_types[0];
+                    let stride =
+                        GpuTypeDetails::round_up(item_details.alignment(), item_details.size());
+                    (index as u64 * stride, item_details)
+                };
+                write_wgsl_bytes(arg, arg_details, &mut bytes[offset as usize..]);
+            }
+        }
+    }
+}
+
 /// A trait implemented for GPU types that have a corresponding CPU type.
 pub trait Gpu: 'static + Sync + Send + Copy {
     /// The CPU type.
@@ -61,14 +109,14 @@ pub trait Gpu: 'static + Sync + Send + Copy {
 }
 
 #[doc(hidden)]
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub enum Wgsl {
     Value(String),
     Constructor(WgslConstructor),
 }
 
 #[doc(hidden)]
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct WgslConstructor {
     pub type_id: TypeId,
     pub args: Vec<Wgsl>,
@@ -81,7 +129,7 @@ pub struct GpuValue {
     #[derive_where(skip)]
     pub(crate) type_id: TypeId,
     pub(crate) root: GpuValueRoot,
-    pub(crate) extensions: [GpuValueExt; MAX_NESTED_FIELDS],
+    pub(crate) path: GpuValuePath,
 }
 
 impl GpuValue {
@@ -90,12 +138,20 @@ impl GpuValue {
         self.extended::<T>(GpuValueExt::FieldPosition(position))
     }
 
-    #[allow(clippy::trivially_copy_pass_by_ref)]
     pub(crate) fn glob<T: Gpu>(id: &'static &'static str) -> Self {
         Self {
             type_id: TypeId::of::<T>(),
             root: GpuValueRoot::Glob(id),
-            extensions: [GpuValueExt::None; MAX_NESTED_FIELDS],
+            path: GpuValuePath::Empty,
+        }
+    }
+
+    #[allow(clippy::cast_possible_truncation)]
+    pub(crate) fn constant(index: usize, type_id: TypeId) -> Self {
+        Self {
+            type_id,
+            root: GpuValueRoot::Constant(index as u32),
+            path: GpuValuePath::Empty,
         }
     }
 
@@ -103,7 +159,7 @@ impl GpuValue {
         Self {
             type_id: TypeId::of::<T>(),
             root: GpuValueRoot::Var(id),
-            extensions: [GpuValueExt::None; MAX_NESTED_FIELDS],
+            path: GpuValuePath::Empty,
         }
     }
 
@@ -117,7 +173,9 @@ impl GpuValue {
 
     pub(crate) fn var_id(self) -> u32 {
         match self.root {
-            GpuValueRoot::Glob(_) => unreachable!("internal error: value should be a local var"),
+            GpuValueRoot::Glob(_) | GpuValueRoot::Constant(_) => {
+                unreachable!("internal error: value should be a local var")
+            }
             GpuValueRoot::Var(id) => id,
         }
     }
@@ -129,17 +187,11 @@ impl GpuValue {
             .expect("internal error: root value should be a glob")
     }
 
-    fn extended<T: Gpu>(mut self, ext: GpuValueExt) -> Self {
-        let updated_ext = self
-            .extensions
-            .iter_mut()
-            .find(|ext| ext == &&GpuValueExt::None)
-            .expect("struct recursion limit reached");
-        *updated_ext = ext;
+    fn extended<T: Gpu>(self, ext: GpuValueExt) -> Self {
         Self {
             type_id: TypeId::of::<T>(),
             root: self.root,
-            extensions: self.extensions,
+            path: self.path.extended(ext),
         }
     }
 }
@@ -147,6 +199,8 @@ impl GpuValue {
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub(crate) enum GpuValueRoot {
     Glob(&'static &'static str), // double reference to reduce size
+    // hidden glob storing a large constant, identified by its index in the app
+    Constant(u32),
     Var(u32),
 }
 
@@ -155,7 +209,120 @@ pub(crate) enum GpuValueExt {
     FieldPosition(u16),
     VecFieldPosition(u8),
     IndexVarId(u32),
-    None,
+}
+
+// Paths are linked lists of interned nodes so that `GpuValue` stays `Copy` whatever the nesting
+// depth, and so that paths are compared without going through their nodes.
+// Paths made only of field accesses only depend on the types, so their nodes are shared by all
+// contexts, like the values of globs that are initialized once per process.
+// Paths containing an array index depend on the context variables, so their nodes are stored in
+// the context and dropped with it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) enum GpuValuePath {
+    Empty,
+    Field(FieldPathNode),
+    Context(u32),
+}
+
+impl GpuValuePath {
+    pub(crate) fn extensions(self, paths: &GpuValuePaths) -> Vec<GpuValueExt> {
+        let mut extensions = vec![];
+        let mut path = self;
+        loop {
+            let node = match path {
+                Self::Empty => break,
+                Self::Field(node) => node.0,
+                Self::Context(id) => paths
+                    .nodes
+                    .get(id as usize)
+                    .expect("internal error: GPU value path from another context"),
+            };
+            extensions.push(node.ext);
+            path = node.parent;
+        }
+        extensions.reverse();
+        extensions
+    }
+
+    fn extended(self, ext: GpuValueExt) -> Self {
+        let node = GpuValuePathNode { parent: self, ext };
+        if matches!(self, Self::Context(_)) || matches!(ext, GpuValueExt::IndexVarId(_)) {
+            Self::Context(GpuContext::run_current(|ctx| ctx.paths.intern(node)))
+        } else {
+            Self::Field(FieldPathNode::intern(node))
+        }
+    }
+}
+
+// Field paths are bounded by the nesting of the types, so their nodes live as long as the process.
+// Each thread caches the nodes it uses to avoid locking the shared interner for each field access.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct FieldPathNode(&'static GpuValuePathNode);
+
+impl FieldPathNode {
+    fn intern(node: GpuValuePathNode) -> Self {
+        static NODES: LazyLock<Mutex<FxHashSet<&'static GpuValuePathNode>>> =
+            LazyLock::new(Mutex::default);
+        thread_local! {
+            static CACHED_NODES: RefCell<FxHashSet<&'static GpuValuePathNode>> = RefCell::default();
+        }
+        CACHED_NODES.with_borrow_mut(|cached_nodes| {
+            if let Some(&node) = cached_nodes.get(&node) {
+                return Self(node);
+            }
+            let mut nodes = NODES
+                .lock()
+                .expect("internal error: cannot lock GPU value paths");
+            let node = if let Some(&node) = nodes.get(&node) {
+                node
+            } else {
+                let node: &'static GpuValuePathNode = Box::leak(Box::new(node));
+                nodes.insert(node);
+                node
+            };
+            cached_nodes.insert(node);
+            Self(node)
+        })
+    }
+}
+
+impl PartialEq for FieldPathNode {
+    fn eq(&self, other: &Self) -> bool {
+        ptr::eq(self.0, other.0)
+    }
+}
+
+impl Eq for FieldPathNode {}
+
+impl Hash for FieldPathNode {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        ptr::hash(self.0, state);
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) struct GpuValuePathNode {
+    parent: GpuValuePath,
+    ext: GpuValueExt,
+}
+
+#[derive(Debug, Default)]
+pub(crate) struct GpuValuePaths {
+    nodes: Vec<GpuValuePathNode>,
+    ids: FxHashMap<GpuValuePathNode, u32>,
+}
+
+impl GpuValuePaths {
+    fn intern(&mut self, node: GpuValuePathNode) -> u32 {
+        if let Some(&id) = self.ids.get(&node) {
+            id
+        } else {
+            let id = u32::try_from(self.nodes.len()).expect("GPU value path limit reached");
+            self.nodes.push(node);
+            self.ids.insert(node, id);
+            id
+        }
+    }
 }
 
 #[doc(hidden)]
@@ -164,8 +331,8 @@ pub struct GpuTypeDetails {
     pub(crate) type_id: TypeId,
     // specified only if the name shouldn't be generated
     pub(crate) name: Option<&'static str>,
-    // specified only for array type
-    pub(crate) array_generics: Option<(Box<GpuTypeDetails>, usize)>,
+    // specified only for array type, the item type being the only field type
+    pub(crate) array_length: Option<usize>,
     // specified only for native types
     pub(crate) size: Option<u64>,
     // specified only for native types
@@ -179,7 +346,7 @@ impl GpuTypeDetails {
         Self {
             type_id: TypeId::of::<T>(),
             name: None,
-            array_generics: None,
+            array_length: None,
             size: None,
             alignment: None,
             field_types,
@@ -208,7 +375,7 @@ impl GpuTypeDetails {
         Self {
             type_id: TypeId::of::<()>(),
             name: None,
-            array_generics: None,
+            array_length: None,
             size: None,
             alignment: None,
             field_types: fields
@@ -218,6 +385,10 @@ impl GpuTypeDetails {
         }
     }
 
+    pub(crate) fn is_struct(&self) -> bool {
+        self.name.is_none()
+    }
+
     pub(crate) fn size(&self) -> u64 {
         if let Some(size) = self.size {
             size
@@ -16,7 +16,7 @@ macro_rules! native_gpu_type {
                 GpuTypeDetails {
                     type_id: TypeId::of::<Self>(),
                     name: Some($wgsl_name),
-                    array_generics: None,
+                    array_length: None,
                     size: Some(4),
                     alignment: Some(4),
                     field_types: vec![],
@@ -46,6 +46,10 @@ impl Cpu for i32 {
         Self::from_ne_bytes([bytes[0], bytes[1], bytes[2], bytes[3]])
     }
 
+    fn write_gpu(&self, bytes: &mut [u8]) {
+        bytes[..4].copy_from_slice(&self.to_ne_bytes());
+    }
+
     fn to_wgsl(&self) -> Wgsl {
         Wgsl::Value(self.to_string())
     }
@@ -58,6 +62,10 @@ impl Cpu for u32 {
         Self::from_ne_bytes([bytes[0], bytes[1], bytes[2], bytes[3]])
     }
 
+    fn write_gpu(&self, bytes: &mut [u8]) {
+        bytes[..4].copy_from_slice(&self.to_ne_bytes());
+    }
+
     fn to_wgsl(&self) -> Wgsl {
         Wgsl::Value(self.to_string())
     }
@@ -70,6 +78,10 @@ impl Cpu for f32 {
         Self::from_ne_bytes([bytes[0], bytes[1], bytes[2], bytes[3]])
     }
 
+    fn write_gpu(&self, bytes: &mut [u8]) {
+        bytes[..4].copy_from_slice(&self.to_ne_bytes());
+    }
+
     fn to_wgsl(&self) -> Wgsl {
         let value = self.to_string();
         Wgsl::Value(if value.contains('.') {
@@ -87,6 +99,10 @@ impl Cpu for bool {
         u32::from_ne_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]) != 0
     }
 
+    fn write_gpu(&self, bytes: &mut [u8]) {
+        u32::from(*self).write_gpu(bytes);
+    }
+
     fn to_wgsl(&self) -> Wgsl {
         Wgsl::Value(u32::from(*self).to_string())
     }
@@ -31,7 +31,7 @@ impl<T: Gpu> Gpu for Range<T> {
         GpuTypeDetails {
             type_id: TypeId::of::<Self>(),
             name: None,
-            array_generics: None,
+            array_length: None,
             size: None,
             alignment: None,
             field_types: vec![T::details(), T::details()],
@@ -60,6 +60,14 @@ impl<T: Cpu> Cpu for ops::Range<T> {
         T::from_gpu(&bytes[..end_offset])..T::from_gpu(&bytes[end_offset..])
     }
 
+    #[allow(clippy::cast_possible_truncation)]
+    fn write_gpu(&self, bytes: &mut [u8]) {
+        let end_offset = Self::Gpu::details().field_offset(1) as usize;
+        let (start_bytes, end_bytes) = bytes.split_at_mut(end_offset);
+        self.start.write_gpu(start_bytes);
+        self.end.write_gpu(end_bytes);
+    }
+
     fn to_wgsl(&self) -> Wgsl {
         Wgsl::Constructor(WgslConstructor {
             type_id: TypeId::of::<Self::Gpu>(),
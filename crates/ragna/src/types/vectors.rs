@@ -76,7 +76,7 @@ macro_rules! simd_type {
                 GpuTypeDetails {
                     type_id: TypeId::of::<Self>(),
                     name: Some($wgsl_name),
-                    array_generics: None,
+                    array_length: None,
                     size: Some(4 * $field_count),
                     alignment: Some(if $field_count == 2 {8} else {16}),
                     field_types: vec![$gpu_item_name::details()],
@@ -126,6 +126,11 @@ macro_rules! simd_type {
                 }
             }
 
+            #[allow(clippy::cast_possible_truncation)]
+            fn write_gpu(&self, bytes: &mut [u8]) {
+                $(self.$field_ident.write_gpu(&mut bytes[$field_byte_range]);)+
+            }
+
             fn to_wgsl(&self) -> Wgsl {
                 Wgsl::Constructor(WgslConstructor {
                     type_id: TypeId::of::<Self::Gpu>(),
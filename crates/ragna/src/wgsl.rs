@@ -1,17 +1,208 @@
 use crate::context::GpuContext;
+use crate::draw::CustomDraw;
 use crate::operations::Operation;
-use crate::types::{GpuTypeDetails, GpuValueExt, GpuValueRoot};
-use crate::{Bool, GpuValue, Wgsl};
+use crate::types::{GpuTypeDetails, GpuValueExt, GpuValuePaths, GpuValueRoot};
+use crate::{camera, target, Bool, Gpu, GpuValue, Wgsl};
 use fxhash::FxHashMap;
 use itertools::Itertools;
 use std::any::TypeId;
 
 const BUFFER_NAME: &str = "buf";
 const BUFFER_TYPE_NAME: &str = "Buf";
+const VERTEX_TYPE_NAME: &str = "RagnaVertex";
+const PIXEL_TEXTURE_NAME: &str = "pixels";
+const SOURCE_TEXTURE_NAME: &str = "ragna_source";
+const SHAPE_SHADER: &str = include_str!("shaders/shape.wgsl");
+const SPRITE_SHADER: &str = include_str!("shaders/sprite.wgsl");
+const TEXT_SHADER: &str = include_str!("shaders/text.wgsl");
+const CAMERA_SHADER: &str = include_str!("shaders/camera.wgsl");
+
+pub(crate) const PIXEL_SHADER: &str = include_str!("shaders/pixels.wgsl");
+
+pub(crate) const VERTEX_VAR: &str = "vertex";
+pub(crate) const SAMPLE_FN_NAME: &str = "ragna_sample";
+pub(crate) const TEXTURE_SIZE_FN_NAME: &str = "ragna_texture_size";
+pub(crate) const WORLD_TO_SCREEN_FN_NAME: &str = "ragna_world_to_screen";
+pub(crate) const SCREEN_TO_WORLD_FN_NAME: &str = "ragna_screen_to_world";
+pub(crate) const SCREEN_TO_NDC_FN_NAME: &str = "ragna_screen_to_ndc";
+pub(crate) const NDC_TO_SCREEN_FN_NAME: &str = "ragna_ndc_to_screen";
+pub(crate) const WORLD_TO_NDC_FN_NAME: &str = "ragna_world_to_ndc";
+pub(crate) const NDC_TO_WORLD_FN_NAME: &str = "ragna_ndc_to_world";
+pub(crate) const SOURCE_COLOR_FN_NAME: &str = "ragna_source_color";
+const IS_IN_VIEWPORT_FN_NAME: &str = "ragna_is_in_viewport";
 
 pub(crate) fn header_code(
     types: &FxHashMap<TypeId, (usize, GpuTypeDetails)>,
     globs: &[GpuValue],
+) -> String {
+    header_code_with_access(types, globs, "read_write")
+}
+
+pub(crate) fn pixel_header_code() -> String {
+    format!(
+        "@group(0) @binding(1)\nvar {PIXEL_TEXTURE_NAME}: texture_storage_2d<rgba8unorm, write>;\n\n"
+    )
+}
+
+pub(crate) fn texture_header_code(texture_count: usize) -> String {
+    let bindings = (0..texture_count)
+        .map(|index| {
+            let texture_binding = texture_binding(index);
+            let sampler_binding = texture_binding + 1;
+            format!(
+                "@group(0) @binding({texture_binding})\nvar t{index}: texture_2d<f32>;\n\n\
+                @group(0) @binding({sampler_binding})\nvar s{index}: sampler;\n\n"
+            )
+        })
+        .join("");
+    // textureSampleLevel is used instead of textureSample as it can be called in non-uniform
+    // control flow and in compute shaders
+    let sample_cases = (0..texture_count)
+        .map(|index| {
+            format!(
+                "        case {index}u: {{ return textureSampleLevel(t{index}, s{index}, uv, 0.); }}\n"
+            )
+        })
+        .join("");
+    let size_cases = (0..texture_count)
+        .map(|index| format!("        case {index}u: {{ return textureDimensions(t{index}); }}\n"))
+        .join("");
+    format!(
+        "{bindings}fn {SAMPLE_FN_NAME}(texture: u32, uv: vec2f) -> vec4f {{\n\
+        \x20   switch texture {{\n{sample_cases}        default: {{ return vec4f(); }}\n    }}\n}}\n\n\
+        fn {TEXTURE_SIZE_FN_NAME}(texture: u32) -> vec2u {{\n\
+        \x20   switch texture {{\n{size_cases}        default: {{ return vec2u(); }}\n    }}\n}}\n\n"
+    )
+}
+
+pub(crate) fn texture_binding(texture_index: usize) -> u32 {
+    (2 + texture_index * 2)
+        .try_into()
+        .expect("internal error: too many textures")
+}
+
+pub(crate) fn camera_header_code(globs: &[GpuValue]) -> String {
+    replace_fn_names(CAMERA_SHADER)
+        .replace(
+            "{{camera}}",
+            &glob_value_code(&camera::CAMERA.value(), globs),
+        )
+        .replace(
+            "{{target_size}}",
+            &glob_value_code(&target::TARGET_SIZE.value(), globs),
+        )
+}
+
+pub(crate) fn render_header_code(
+    types: &FxHashMap<TypeId, (usize, GpuTypeDetails)>,
+    globs: &[GpuValue],
+) -> String {
+    // vertex shaders cannot access storage buffers in write mode
+    header_code_with_access(types, globs, "read")
+}
+
+pub(crate) fn shape_shader_code(shapes: &GpuValue, globs: &[GpuValue]) -> String {
+    replace_fn_names(SHAPE_SHADER).replace("{{shapes}}", &glob_value_code(shapes, globs))
+}
+
+pub(crate) fn sprite_shader_code(sprites: &GpuValue, globs: &[GpuValue]) -> String {
+    replace_fn_names(SPRITE_SHADER).replace("{{sprites}}", &glob_value_code(sprites, globs))
+}
+
+pub(crate) fn text_shader_code(text: &GpuValue, globs: &[GpuValue]) -> String {
+    replace_fn_names(TEXT_SHADER).replace("{{text}}", &glob_value_code(text, globs))
+}
+
+fn replace_fn_names(code: &str) -> String {
+    code.replace("{{sample_fn}}", SAMPLE_FN_NAME)
+        .replace("{{texture_size_fn}}", TEXTURE_SIZE_FN_NAME)
+        .replace("{{world_to_screen_fn}}", WORLD_TO_SCREEN_FN_NAME)
+        .replace("{{screen_to_world_fn}}", SCREEN_TO_WORLD_FN_NAME)
+        .replace("{{screen_to_ndc_fn}}", SCREEN_TO_NDC_FN_NAME)
+        .replace("{{ndc_to_screen_fn}}", NDC_TO_SCREEN_FN_NAME)
+        .replace("{{world_to_ndc_fn}}", WORLD_TO_NDC_FN_NAME)
+        .replace("{{ndc_to_world_fn}}", NDC_TO_WORLD_FN_NAME)
+        .replace("{{is_in_viewport_fn}}", IS_IN_VIEWPORT_FN_NAME)
+}
+
+pub(crate) fn render_shader_code(
+    draw: &CustomDraw,
+    types: &FxHashMap<TypeId, (usize, GpuTypeDetails)>,
+    globs: &[GpuValue],
+) -> String {
+    custom_shader_code(draw, types, globs)
+}
+
+pub(crate) fn post_process_shader_code(
+    draw: &CustomDraw,
+    types: &FxHashMap<TypeId, (usize, GpuTypeDetails)>,
+    globs: &[GpuValue],
+) -> String {
+    format!(
+        "@group(0) @binding(1)\nvar {SOURCE_TEXTURE_NAME}: texture_2d<f32>;\n\n\
+        fn {SOURCE_COLOR_FN_NAME}(position: vec2f) -> vec4f {{\n\
+        \x20   let max_position = vec2i(textureDimensions({SOURCE_TEXTURE_NAME})) - 1;\n\
+        \x20   let clamped_position = clamp(vec2i(floor(position)), vec2i(), max_position);\n\
+        \x20   return textureLoad({SOURCE_TEXTURE_NAME}, clamped_position, 0);\n}}\n\n{}",
+        custom_shader_code(draw, types, globs)
+    )
+}
+
+fn custom_shader_code(
+    draw: &CustomDraw,
+    types: &FxHashMap<TypeId, (usize, GpuTypeDetails)>,
+    globs: &[GpuValue],
+) -> String {
+    let output_type = &types[&draw.vertex_output.type_id].1;
+    let output_fields: Vec<_> = if output_type.is_struct() {
+        output_type
+            .field_types
+            .iter()
+            .enumerate()
+            .map(|(index, type_)| (format!(".{}", field_name(index)), type_.type_id))
+            .collect()
+    } else {
+        vec![(String::new(), output_type.type_id)]
+    };
+    let vertex_fields = output_fields
+        .iter()
+        .enumerate()
+        .map(|(index, (_, type_id))| {
+            let attribute = if index == 0 {
+                "@builtin(position)".into()
+            } else if is_integer_type(*type_id, types) {
+                format!("@location({}) @interpolate(flat)", index - 1)
+            } else {
+                format!("@location({})", index - 1)
+            };
+            let field_name = field_name(index);
+            let type_name = type_name(*type_id, types);
+            format!("    {attribute} {field_name}: {type_name},")
+        })
+        .join("\n");
+    let vertex_output = value_code(&draw.vertex_output, &draw.vertex_ctx.paths, globs);
+    let vertex_output_args = output_fields
+        .iter()
+        .map(|(field, _)| format!("{vertex_output}{field}"))
+        .join(", ");
+    format!(
+        "struct {VERTEX_TYPE_NAME} {{\n{vertex_fields}\n}}\n\n\
+        @vertex\nfn vs_main(\
+        @builtin(vertex_index) vertex_index: u32, \
+        @builtin(instance_index) instance_index: u32\
+        ) -> {VERTEX_TYPE_NAME} {{\n{}\n    return {VERTEX_TYPE_NAME}({vertex_output_args});\n}}\n\n\
+        @fragment\nfn fs_main({VERTEX_VAR}: {VERTEX_TYPE_NAME}) -> @location(0) vec4f {{\n\
+        {}\n    return {};\n}}",
+        operations_code(&draw.vertex_ctx, types, globs),
+        operations_code(&draw.fragment_ctx, types, globs),
+        value_code(&draw.fragment_output, &draw.fragment_ctx.paths, globs),
+    )
+}
+
+fn header_code_with_access(
+    types: &FxHashMap<TypeId, (usize, GpuTypeDetails)>,
+    globs: &[GpuValue],
+    access: &str,
 ) -> String {
     if globs.is_empty() {
         String::new()
@@ -30,7 +221,7 @@ pub(crate) fn header_code(
             .map(|(_, type_)| struct_(type_, types))
             .join("\n");
         format!(
-            "@group(0) @binding(0)\nvar<storage, read_write> {BUFFER_NAME}: {BUFFER_TYPE_NAME};\n\n\
+            "@group(0) @binding(0)\nvar<storage, {access}> {BUFFER_NAME}: {BUFFER_TYPE_NAME};\n\n\
             struct {BUFFER_TYPE_NAME} {{\n{buffer_fields}\n}}\n\n\
             {structs}\n\n",
         )
@@ -43,25 +234,22 @@ pub(crate) fn compute_shader_code(
     globs: &[GpuValue],
 ) -> String {
     format!(
-        "@compute @workgroup_size(1, 1, 1)\nfn main() {{\n{}\n{}\n}}",
-        globs
-            .iter()
-            .enumerate()
-            .map(|(index, glob)| format!(
-                // force the use of global variables to avoid pipeline creation error
-                "    var _vg{} = {}.{};",
-                index,
-                BUFFER_NAME,
-                glob_name(glob, globs)
-            ))
-            .join("\n"),
-        ctx.operations
-            .iter()
-            .map(|operation| operation_code(operation, types, globs))
-            .join("\n")
+        "@compute @workgroup_size(1, 1, 1)\nfn main() {{\n{}\n}}",
+        operations_code(ctx, types, globs)
     )
 }
 
+fn operations_code(
+    ctx: &GpuContext,
+    types: &FxHashMap<TypeId, (usize, GpuTypeDetails)>,
+    globs: &[GpuValue],
+) -> String {
+    ctx.operations
+        .iter()
+        .map(|operation| operation_code(operation, &ctx.paths, types, globs))
+        .join("\n")
+}
+
 fn struct_(
     type_details: &GpuTypeDetails,
     types: &FxHashMap<TypeId, (usize, GpuTypeDetails)>,
@@ -82,6 +270,7 @@ fn struct_(
 
 fn operation_code(
     operation: &Operation,
+    paths: &GpuValuePaths,
     types: &FxHashMap<TypeId, (usize, GpuTypeDetails)>,
     globs: &[GpuValue],
 ) -> String {
@@ -92,46 +281,51 @@ fn operation_code(
             format!("    var {var_name}: {type_name};")
         }
         Operation::AssignVar(op) => {
-            let left = value_code(&op.left_value, globs);
-            let right = value_code(&op.right_value, globs);
+            let left = value_code(&op.left_value, paths, globs);
+            let right = value_code(&op.right_value, paths, globs);
             format!("    {left} = {right};")
         }
         Operation::ConstantAssignVar(op) => {
-            let var_name = value_code(&op.left_value, globs);
-            let value = wgsl_to_string(&op.right_value, types);
+            let var_name = value_code(&op.left_value, paths, globs);
+            let value = wgsl_to_string(&op.right_value.to_wgsl(), types);
             format!("    {var_name} = {value};")
         }
         Operation::Unary(op) => {
-            let var_name = value_code(&op.var, globs);
-            let value = function_arg(&op.value, globs, true);
+            let var_name = value_code(&op.var, paths, globs);
+            let value = function_arg(&op.value, paths, globs, true);
             let operation = format!("{}{value}", op.operator);
             let expr = returned_value(&op.var, operation, true, types);
             format!("    {var_name} = {expr};")
         }
         Operation::Binary(op) => {
-            let var_name = value_code(&op.var, globs);
-            let left_value = function_arg(&op.left_value, globs, true);
-            let right_value = function_arg(&op.right_value, globs, true);
+            let var_name = value_code(&op.var, paths, globs);
+            let left_value = function_arg(&op.left_value, paths, globs, true);
+            let right_value = function_arg(&op.right_value, paths, globs, true);
             let operation = format!("{left_value} {} {right_value}", op.operator);
             let expr = returned_value(&op.var, operation, true, types);
             format!("    {var_name} = {expr};")
         }
         Operation::FnCall(op) => {
-            let var_name = value_code(&op.var, globs);
+            let var_name = value_code(&op.var, paths, globs);
             let fn_name = op.fn_name;
             let args = op
                 .args
                 .iter()
-                .map(|value| function_arg(value, globs, op.is_supporting_bool))
+                .map(|value| function_arg(value, paths, globs, op.is_supporting_bool))
                 .join(", ");
             let operation = format!("{fn_name}({args})");
             let expr = returned_value(&op.var, operation, op.is_supporting_bool, types);
             format!("    {var_name} = {expr};")
         }
         Operation::IfBlock(op) => {
-            let condition = value_code(&op.condition, globs);
+            let condition = value_code(&op.condition, paths, globs);
             format!("    if (bool({condition})) {{")
         }
+        Operation::SetPixel(op) => {
+            let position = value_code(&op.position, paths, globs);
+            let color = value_code(&op.color, paths, globs);
+            format!("    textureStore({PIXEL_TEXTURE_NAME}, {position}, {color});")
+        }
         Operation::ElseBlock => "    } else {".to_string(),
         Operation::LoopBlock => "    loop {".to_string(),
         Operation::EndBlock => "    }".to_string(),
@@ -140,11 +334,16 @@ fn operation_code(
     }
 }
 
-fn function_arg(value: &GpuValue, globs: &[GpuValue], is_supporting_bool: bool) -> String {
+fn function_arg(
+    value: &GpuValue,
+    paths: &GpuValuePaths,
+    globs: &[GpuValue],
+    is_supporting_bool: bool,
+) -> String {
     if value.type_id == TypeId::of::<Bool>() && is_supporting_bool {
-        format!("bool({})", value_code(value, globs))
+        format!("bool({})", value_code(value, paths, globs))
     } else {
-        value_code(value, globs)
+        value_code(value, paths, globs)
     }
 }
 
@@ -163,20 +362,26 @@ fn returned_value(
     }
 }
 
-fn value_code(value: &GpuValue, globs: &[GpuValue]) -> String {
+// the paths of glob values never depend on a context
+fn glob_value_code(value: &GpuValue, globs: &[GpuValue]) -> String {
+    value_code(value, &GpuValuePaths::default(), globs)
+}
+
+fn value_code(value: &GpuValue, paths: &GpuValuePaths, globs: &[GpuValue]) -> String {
     let root = match value.root {
-        GpuValueRoot::Glob(_) => {
+        GpuValueRoot::Glob(_) | GpuValueRoot::Constant(_) => {
             let glob_name = glob_name(value.root_value(globs), globs);
             format!("{BUFFER_NAME}.{glob_name}")
         }
         GpuValueRoot::Var(id) => var_name(id),
     };
     let extensions = value
-        .extensions
-        .iter()
-        .filter_map(|ext| match ext {
-            GpuValueExt::FieldPosition(pos) => Some(format!(".{}", field_name(*pos as usize))),
-            GpuValueExt::VecFieldPosition(pos) => Some(format!(
+        .path
+        .extensions(paths)
+        .into_iter()
+        .map(|ext| match ext {
+            GpuValueExt::FieldPosition(pos) => format!(".{}", field_name(pos as usize)),
+            GpuValueExt::VecFieldPosition(pos) => format!(
                 ".{}",
                 match pos {
                     0 => "x",
@@ -184,9 +389,8 @@ fn value_code(value: &GpuValue, globs: &[GpuValue]) -> String {
                     2 => "z",
                     _ => "w",
                 }
-            )),
-            GpuValueExt::IndexVarId(id) => Some(format!("[{}]", var_name(*id))),
-            GpuValueExt::None => None,
+            ),
+            GpuValueExt::IndexVarId(id) => format!("[{}]", var_name(id)),
         })
         .join("");
     format!("{root}{extensions}")
@@ -214,18 +418,25 @@ fn type_name(type_id: TypeId, types: &FxHashMap<TypeId, (usize, GpuTypeDetails)>
     } else {
         format!("T{id}")
     };
-    if let Some((item_type, length)) = &details.array_generics {
-        let item_type_name = type_name(item_type.type_id, types);
+    if let Some(length) = details.array_length {
+        let item_type_name = type_name(details.field_types[0].type_id, types);
         format!("{name}<{item_type_name}, {length}>")
     } else {
         name
     }
 }
 
-fn field_name(field_index: usize) -> String {
+pub(crate) fn field_name(field_index: usize) -> String {
     format!("f{field_index}")
 }
 
+fn is_integer_type(type_id: TypeId, types: &FxHashMap<TypeId, (usize, GpuTypeDetails)>) -> bool {
+    let type_name = type_name(type_id, types);
+    type_name == "u32"
+        || type_name == "i32"
+        || (type_name.starts_with("vec") && (type_name.ends_with('u') || type_name.ends_with('i')))
+}
+
 fn wgsl_to_string(wgsl: &Wgsl, types: &FxHashMap<TypeId, (usize, GpuTypeDetails)>) -> String {
     match wgsl {
         Wgsl::Value(value) => value.clone(),
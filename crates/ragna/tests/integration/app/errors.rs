@@ -0,0 +1,87 @@
+use ragna::{App, RagnaError, TextureTargetConfig};
+
+#[test]
+pub fn create_app_with_invalid_shader() {
+    let result = App::default()
+        .with_module(invalid_shader::register)
+        .try_texture((1, 1));
+    let Err(RagnaError::ShaderValidation {
+        message,
+        wgsl_excerpt,
+    }) = result
+    else {
+        panic!("invalid result")
+    };
+    assert!(message.contains("unknown_fn"));
+    assert!(wgsl_excerpt.contains("unknown_fn("));
+    assert!(wgsl_excerpt.lines().count() <= 7);
+}
+
+#[test]
+pub fn create_app_with_too_large_buffer() {
+    let result = App::default()
+        .with_module(too_large_buffer::register)
+        .try_texture((1, 1));
+    let Err(RagnaError::BufferTooLarge { size, max_size }) = result else {
+        panic!("invalid result")
+    };
+    assert_eq!(size, 4 * 100_000_000 + super::builtin_globs_size());
+    assert!(max_size < size);
+}
+
+#[test]
+pub fn create_app_with_unsupported_sample_count() {
+    let config = TextureTargetConfig {
+        samples: 3,
+        ..TextureTargetConfig::default()
+    };
+    let result = App::default().try_texture_with((1, 1), config);
+    let Err(RagnaError::UnsupportedSampleCount { samples }) = result else {
+        panic!("invalid result")
+    };
+    assert_eq!(samples, 3);
+}
+
+#[test]
+pub fn create_app_with_sample_count_not_enabled_on_device() {
+    let config = TextureTargetConfig {
+        samples: 8,
+        ..TextureTargetConfig::default()
+    };
+    let result = App::default().try_texture_with((1, 1), config);
+    assert_eq!(
+        result.err(),
+        Some(RagnaError::UnsupportedSampleCount { samples: 8 })
+    );
+}
+
+#[test]
+#[should_panic = "cannot initialize texture application"]
+pub fn create_app_with_invalid_shader_without_error_handling() {
+    App::default()
+        .with_module(invalid_shader::register)
+        .texture((1, 1));
+}
+
+#[ragna::gpu]
+mod invalid_shader {
+    use ragna::I32;
+
+    static GLOB: I32 = 0;
+
+    extern "wgsl" {
+        fn unknown_fn(value: I32) -> I32;
+    }
+
+    #[compute]
+    fn run() {
+        *GLOB = unknown_fn(*GLOB);
+    }
+}
+
+#[ragna::gpu]
+mod too_large_buffer {
+    use ragna::{Array, U32};
+
+    static GLOB: Array<U32, 100_000_000> = [0u; 100_000_000];
+}
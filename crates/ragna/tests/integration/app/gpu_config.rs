@@ -0,0 +1,67 @@
+use ragna::{App, Backends, Features, GpuConfig, Limits, RagnaError};
+
+#[test]
+pub fn run_app_with_custom_limits() {
+    let app = App::default()
+        .with_module(gpu::register)
+        .with_gpu_config(GpuConfig {
+            required_limits: Limits::downlevel_defaults(),
+            ..GpuConfig::default()
+        })
+        .texture((1, 1))
+        .run(1);
+    assert_eq!(app.read(*gpu::GLOB), Some([1, 2, 3, 4]));
+}
+
+#[test]
+pub fn run_app_without_backend() {
+    let result = App::default()
+        .with_gpu_config(GpuConfig {
+            backends: Backends::empty(),
+            ..GpuConfig::default()
+        })
+        .try_texture((1, 1));
+    assert!(matches!(result, Err(RagnaError::AdapterNotFound)));
+}
+
+#[test]
+pub fn run_app_with_unsupported_features() {
+    let result = App::default()
+        .with_gpu_config(GpuConfig {
+            required_features: Features::all(),
+            ..GpuConfig::default()
+        })
+        .try_texture((1, 1));
+    assert!(matches!(
+        result,
+        Err(RagnaError::DeviceRequestFailed { .. })
+    ));
+}
+
+#[test]
+pub fn run_app_with_too_low_limits() {
+    let result = App::default()
+        .with_module(gpu::register)
+        .with_gpu_config(GpuConfig {
+            required_limits: Limits {
+                max_storage_buffer_binding_size: 8,
+                ..Limits::default()
+            },
+            ..GpuConfig::default()
+        })
+        .try_texture((1, 1));
+    assert_eq!(
+        result.err(),
+        Some(RagnaError::BufferTooLarge {
+            size: 16 + super::builtin_globs_size(),
+            max_size: 8
+        })
+    );
+}
+
+#[ragna::gpu]
+mod gpu {
+    use ragna::{Array, U32};
+
+    pub(crate) static GLOB: Array<U32, 4> = [1u, 2u, 3u, 4u];
+}
@@ -1,3 +1,28 @@
+use ragna::{App, GpuConfig, Limits, RagnaError};
+
 pub mod empty;
+pub mod errors;
+pub mod gpu_config;
 pub mod multiple_runs;
+pub mod ranges;
 pub mod read;
+pub mod stats;
+pub mod threads;
+pub mod write;
+
+// size in bytes of the globs registered by default in any application
+fn builtin_globs_size() -> u64 {
+    let result = App::default()
+        .with_gpu_config(GpuConfig {
+            required_limits: Limits {
+                max_storage_buffer_binding_size: 1,
+                ..Limits::default()
+            },
+            ..GpuConfig::default()
+        })
+        .try_texture((1, 1));
+    let Err(RagnaError::BufferTooLarge { size, .. }) = result else {
+        panic!("invalid result")
+    };
+    size
+}
@@ -0,0 +1,80 @@
+use ragna::{f32x3, App};
+
+#[test]
+pub fn read_range() {
+    let app = App::default()
+        .with_module(gpu::register)
+        .texture((1, 1))
+        .run(1);
+    assert_eq!(app.read_range(*gpu::ITEMS, 2..5), Some(vec![2, 3, 4]));
+    assert_eq!(app.read_range(*gpu::ITEMS, 9..10), Some(vec![9]));
+    assert_eq!(app.read_range(*gpu::ITEMS, 3..3), Some(vec![]));
+    assert_eq!(
+        app.read_range(*gpu::VECTORS, 1..2),
+        Some(vec![f32x3 {
+            x: 4.,
+            y: 5.,
+            z: 6.
+        }])
+    );
+}
+
+#[test]
+pub fn read_range_of_not_registered_glob() {
+    let app = App::default().texture((1, 1)).run(1);
+    assert_eq!(app.read_range(*gpu::ITEMS, 2..5), None);
+}
+
+#[test]
+#[should_panic = "range 8..11 out of bounds for array of length 10"]
+pub fn read_range_out_of_bounds() {
+    let app = App::default().with_module(gpu::register).texture((1, 1));
+    let _ = app.read_range(*gpu::ITEMS, 8..11);
+}
+
+#[test]
+pub fn write_range() {
+    let vector = f32x3 {
+        x: 7.,
+        y: 8.,
+        z: 9.,
+    };
+    let app = App::default()
+        .with_module(gpu::register)
+        .texture((1, 1))
+        .write_range(*gpu::ITEMS, 7, &[70, 80])
+        .write_range(*gpu::VECTORS, 0, &[vector])
+        .run(1);
+    assert_eq!(
+        app.read(*gpu::ITEMS),
+        Some([0, 1, 2, 3, 4, 5, 6, 70, 80, 9])
+    );
+    assert_eq!(
+        app.read(*gpu::VECTORS),
+        Some([
+            vector,
+            f32x3 {
+                x: 4.,
+                y: 5.,
+                z: 6.
+            }
+        ])
+    );
+}
+
+#[test]
+#[should_panic = "range 9..11 out of bounds for array of length 10"]
+pub fn write_range_out_of_bounds() {
+    let _app = App::default()
+        .with_module(gpu::register)
+        .texture((1, 1))
+        .write_range(*gpu::ITEMS, 9, &[1, 2]);
+}
+
+#[ragna::gpu]
+mod gpu {
+    use ragna::{Array, F32x3, U32};
+
+    pub(super) static ITEMS: Array<U32, 10> = [0u, 1u, 2u, 3u, 4u, 5u, 6u, 7u, 8u, 9u];
+    pub(super) static VECTORS: Array<F32x3, 2> = [F32x3::new(1., 2., 3.), F32x3::new(4., 5., 6.)];
+}
@@ -1,4 +1,8 @@
-use ragna::{App, Cpu, Glob};
+use futures::executor;
+use ragna::{f32x3, App, Cpu, Glob};
+use std::task::Poll;
+use std::thread;
+use std::time::Duration;
 
 #[test]
 pub fn read_uninitialized() {
@@ -40,12 +44,121 @@ pub fn read_not_registered_external_glob() {
     assert_eq!(app.read(*glob), None);
 }
 
+#[test]
+pub fn read_many_globs() {
+    let glob = Glob::new(|| ::ragna::create_glob(&""), || 0.to_gpu());
+    let app = App::default()
+        .with_module(gpu::register)
+        .texture((1, 1))
+        .run(1);
+    assert_eq!(
+        app.read_many((*gpu::USED_GLOB, *glob, *gpu::UNUSED_GLOB, *gpu::VECTOR)),
+        (
+            Some(10),
+            None,
+            Some(20),
+            Some(f32x3 {
+                x: 1.,
+                y: 2.,
+                z: 3.
+            })
+        )
+    );
+    assert_eq!(app.read_many((*glob,)), (None,));
+}
+
+#[test]
+pub fn read_many_uninitialized() {
+    let app = App::default().texture((1, 1));
+    assert_eq!(app.read_many((*gpu::USED_GLOB,)), (None,));
+}
+
+#[test]
+pub fn read_all_globs() {
+    let glob = Glob::new(|| ::ragna::create_glob(&""), || 0.to_gpu());
+    let app = App::default()
+        .with_module(gpu::register)
+        .texture((1, 1))
+        .run(1);
+    let snapshot = app.read_all();
+    assert_eq!(snapshot.read(*gpu::USED_GLOB), Some(10));
+    assert_eq!(snapshot.read(*gpu::UNUSED_GLOB), Some(20));
+    assert_eq!(
+        snapshot.read(*gpu::VECTOR),
+        Some(f32x3 {
+            x: 1.,
+            y: 2.,
+            z: 3.
+        })
+    );
+    assert_eq!(snapshot.read(*glob), None);
+}
+
+#[test]
+pub fn read_all_uninitialized() {
+    let app = App::default().texture((1, 1));
+    assert_eq!(app.read_all().read(*gpu::USED_GLOB), None);
+}
+
+#[test]
+pub fn read_async() {
+    let glob = Glob::new(|| ::ragna::create_glob(&""), || 0.to_gpu());
+    let app = App::default()
+        .with_module(gpu::register)
+        .texture((1, 1))
+        .run(1);
+    assert_eq!(
+        executor::block_on(app.read_async(*gpu::USED_GLOB)),
+        Some(10)
+    );
+    assert_eq!(executor::block_on(app.read_async(*glob)), None);
+}
+
+#[test]
+pub fn poll_async_read() {
+    let app = App::default()
+        .with_module(gpu::register)
+        .texture((1, 1))
+        .run(1);
+    let mut read = app.read_async(*gpu::VECTOR);
+    let app = app.run(1);
+    let value = (0..1_000)
+        .find_map(|_| {
+            if let Poll::Ready(value) = read.try_read() {
+                Some(value)
+            } else {
+                thread::sleep(Duration::from_millis(10));
+                None
+            }
+        })
+        .expect("read not completed");
+    assert_eq!(
+        value,
+        Some(f32x3 {
+            x: 1.,
+            y: 2.,
+            z: 3.
+        })
+    );
+    assert_eq!(app.read(*gpu::USED_GLOB), Some(10));
+}
+
+#[test]
+#[should_panic = "read already completed"]
+pub fn poll_completed_async_read() {
+    let app = App::default().texture((1, 1)).run(1);
+    let mut read = app.read_async(*gpu::USED_GLOB);
+    assert_eq!(read.try_read(), Poll::Ready(None));
+    let _ = read.try_read();
+}
+
 #[ragna::gpu]
 mod gpu {
-    use ragna::I32;
+    use ragna::{F32x3, I32};
 
     pub(super) static UNUSED_GLOB: I32 = 20;
     pub(super) static USED_GLOB: I32 = 0;
+    pub(super) static VECTOR: F32x3 = F32x3::new(1., 2., 3.);
 
     #[compute]
     fn run() {
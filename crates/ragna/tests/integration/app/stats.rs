@@ -0,0 +1,26 @@
+use ragna::App;
+
+#[test]
+pub fn retrieve_stats_before_run() {
+    let app = App::default().texture((1, 1));
+    let stats = app.stats();
+    assert_eq!(stats.step_count, 0);
+    assert!(stats.last_duration.is_zero());
+    assert!(stats.average_duration.is_zero());
+    assert!(stats.min_duration.is_zero());
+    assert!(stats.max_duration.is_zero());
+}
+
+#[test]
+pub fn retrieve_stats_after_run() {
+    let app = App::default().texture((1, 1)).run(3);
+    let stats = app.stats();
+    assert_eq!(stats.step_count, 3);
+    assert!(stats.min_duration <= stats.last_duration);
+    assert!(stats.last_duration <= stats.max_duration);
+    assert!(stats.min_duration <= stats.average_duration);
+    assert!(stats.average_duration <= stats.max_duration);
+    assert!(!stats.max_duration.is_zero());
+    let app = app.run(2);
+    assert_eq!(app.stats().step_count, 5);
+}
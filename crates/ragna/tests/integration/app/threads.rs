@@ -0,0 +1,62 @@
+use ragna::{App, Cpu};
+use std::{panic, thread};
+
+#[test]
+pub fn run_apps_in_parallel() {
+    let handles: Vec<_> = (0..16)
+        .map(|_| {
+            thread::spawn(|| {
+                App::default()
+                    .with_module(gpu::register)
+                    .texture((1, 1))
+                    .run(1)
+                    .read(*gpu::GLOB)
+            })
+        })
+        .collect();
+    for handle in handles {
+        assert_eq!(handle.join().unwrap(), Some(20));
+    }
+}
+
+#[test]
+pub fn run_app_built_in_another_thread() {
+    let app = thread::spawn(|| App::default().with_module(gpu::register))
+        .join()
+        .unwrap();
+    let app = app.texture((1, 1)).run(1);
+    assert_eq!(app.read(*gpu::GLOB), Some(20));
+}
+
+#[test]
+pub fn run_app_after_panic_during_tracing() {
+    let result = panic::catch_unwind(|| App::default().with_compute(|| panic!("tracing failed")));
+    assert!(result.is_err());
+    let result = panic::catch_unwind(|| 0_u32.to_gpu());
+    assert!(result.is_err());
+    let app = App::default()
+        .with_module(gpu::register)
+        .texture((1, 1))
+        .run(1);
+    assert_eq!(app.read(*gpu::GLOB), Some(20));
+}
+
+#[ragna::gpu]
+mod gpu {
+    use ragna::I32;
+
+    pub(crate) static GLOB: I32 = 10;
+
+    #[compute]
+    fn run() {
+        *GLOB = compute_value();
+    }
+
+    fn compute_value() -> I32 {
+        let value = 0_i32;
+        for _ in 0u..10u {
+            value += 2_i32;
+        }
+        value
+    }
+}
@@ -0,0 +1,96 @@
+use ragna::{f32x3, u32x2, App};
+
+#[test]
+pub fn write_before_first_step() {
+    let app = App::default()
+        .with_module(gpu::register)
+        .texture((1, 1))
+        .write(*gpu::INPUT, &5)
+        .run(1);
+    assert_eq!(app.read(*gpu::INPUT), Some(5));
+    assert_eq!(app.read(*gpu::DOUBLED), Some(10));
+}
+
+#[test]
+pub fn write_between_steps() {
+    let app = App::default()
+        .with_module(gpu::register)
+        .texture((1, 1))
+        .run(1)
+        .write(*gpu::INPUT, &7);
+    assert_eq!(app.read(*gpu::INPUT), Some(7));
+    assert_eq!(app.read(*gpu::DOUBLED), Some(2));
+    let app = app.run(1);
+    assert_eq!(app.read(*gpu::DOUBLED), Some(14));
+}
+
+#[test]
+pub fn write_composite_values() {
+    let vectors = [
+        f32x3 {
+            x: 1.,
+            y: 2.,
+            z: 3.,
+        },
+        f32x3 {
+            x: 4.,
+            y: 5.,
+            z: 6.,
+        },
+    ];
+    let config = gpu::ConfigCpu {
+        enabled: true,
+        size: u32x2 { x: 3, y: 4 },
+        offset: f32x3 {
+            x: 0.5,
+            y: 1.5,
+            z: 2.5,
+        },
+        range: 2..8,
+    };
+    let app = App::default()
+        .with_module(gpu::register)
+        .texture((1, 1))
+        .write(*gpu::VECTORS, &vectors)
+        .write(*gpu::CONFIG, &config)
+        .run(1);
+    assert_eq!(app.read(*gpu::VECTORS), Some(vectors));
+    let read_config = app.read(*gpu::CONFIG).unwrap();
+    assert!(read_config.enabled);
+    assert_eq!(read_config.size, config.size);
+    assert_eq!(read_config.offset, config.offset);
+    assert_eq!(read_config.range, 2..8);
+}
+
+#[test]
+pub fn write_not_registered_glob() {
+    let app = App::default().texture((1, 1)).write(*gpu::INPUT, &5).run(1);
+    assert_eq!(app.read(*gpu::INPUT), None);
+}
+
+#[ragna::gpu]
+mod gpu {
+    use ragna::{Array, Bool, F32x3, Range, U32x2, I32, U32};
+
+    pub(super) struct Config {
+        pub(super) enabled: Bool,
+        pub(super) size: U32x2,
+        pub(super) offset: F32x3,
+        pub(super) range: Range<U32>,
+    }
+
+    pub(super) static INPUT: I32 = 1;
+    pub(super) static DOUBLED: I32 = 0;
+    pub(super) static VECTORS: Array<F32x3, 2> = [F32x3::new(0., 0., 0.), F32x3::new(0., 0., 0.)];
+    pub(super) static CONFIG: Config = Config {
+        enabled: false,
+        size: U32x2::new(0u, 0u),
+        offset: F32x3::new(0., 0., 0.),
+        range: 0u..0u,
+    };
+
+    #[compute]
+    fn run() {
+        *DOUBLED = *INPUT * 2;
+    }
+}
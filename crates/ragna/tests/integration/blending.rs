@@ -0,0 +1,190 @@
+use ragna::{assert_same_texture, App, BlendMode, DrawConfig, SamplerConfig};
+
+#[test]
+pub fn draw_with_default_config() {
+    let app = App::default()
+        .with_module(gpu::register)
+        .with_shapes(&gpu::FRONT_SHAPES)
+        .with_shapes(&gpu::BACK_SHAPES)
+        .texture((4, 4))
+        .with_background_color((0., 0., 1., 1.))
+        .run(1);
+    assert_same_texture("blending#default", &app.read_target());
+}
+
+#[test]
+pub fn draw_without_depth_test() {
+    let app = App::default()
+        .with_module(gpu::register)
+        .with_shapes(&gpu::FRONT_SHAPES)
+        .with_shapes_config(
+            &gpu::BACK_SHAPES,
+            DrawConfig {
+                depth_test: false,
+                ..DrawConfig::default()
+            },
+        )
+        .texture((4, 4))
+        .with_background_color((0., 0., 1., 1.))
+        .run(1);
+    assert_same_texture("blending#alpha", &app.read_target());
+}
+
+#[test]
+pub fn draw_without_depth_write() {
+    let app = App::default()
+        .with_module(gpu::register)
+        .with_shapes_config(
+            &gpu::FRONT_SHAPES,
+            DrawConfig {
+                depth_write: false,
+                ..DrawConfig::default()
+            },
+        )
+        .with_shapes(&gpu::BACK_SHAPES)
+        .texture((4, 4))
+        .with_background_color((0., 0., 1., 1.))
+        .run(1);
+    assert_same_texture("blending#alpha", &app.read_target());
+}
+
+#[test]
+pub fn draw_with_additive_blending() {
+    let app = App::default()
+        .with_module(gpu::register)
+        .with_shapes(&gpu::FRONT_SHAPES)
+        .with_shapes_config(
+            &gpu::BACK_SHAPES,
+            DrawConfig {
+                blend: BlendMode::Additive,
+                depth_test: false,
+                depth_write: false,
+            },
+        )
+        .texture((4, 4))
+        .with_background_color((0., 0., 1., 1.))
+        .run(1);
+    assert_same_texture("blending#additive", &app.read_target());
+}
+
+#[test]
+pub fn draw_with_replace_blending() {
+    let app = App::default()
+        .with_module(gpu::register)
+        .with_shapes(&gpu::FRONT_SHAPES)
+        .with_shapes_config(
+            &gpu::BACK_SHAPES,
+            DrawConfig {
+                blend: BlendMode::Replace,
+                depth_test: false,
+                depth_write: true,
+            },
+        )
+        .texture((4, 4))
+        .with_background_color((0., 0., 1., 1.))
+        .run(1);
+    let target = app.read_target();
+    assert_eq!(target.buffer[3], 128);
+    assert_same_texture("blending#replace", &target);
+}
+
+#[test]
+pub fn draw_in_offscreen_target_with_config() {
+    let app = App::default()
+        .with_module(gpu::register)
+        .with_offscreen_target(
+            "layers",
+            &gpu::LAYERS,
+            (4, 4),
+            SamplerConfig::default(),
+            |app| {
+                app.with_shapes(&gpu::FRONT_SHAPES).with_shapes_config(
+                    &gpu::BACK_SHAPES,
+                    DrawConfig {
+                        blend: BlendMode::Additive,
+                        depth_test: false,
+                        depth_write: false,
+                    },
+                )
+            },
+        )
+        .texture((4, 4))
+        .run(1);
+    assert_same_texture("blending#offscreen", &app.read_texture("layers"));
+}
+
+#[test]
+pub fn draw_overlapping_translucent_instances() {
+    let draw = |shapes, config| {
+        App::default()
+            .with_module(gpu::register)
+            .with_shapes_config(shapes, config)
+            .texture((4, 4))
+            .with_background_color((0., 0., 1., 1.))
+            .run(1)
+            .read_target()
+    };
+    let front_only = draw(&gpu::TRANSLUCENT_FRONT_SHAPES, DrawConfig::default());
+    let overlapping = draw(&gpu::TRANSLUCENT_SHAPES, DrawConfig::default());
+    assert_eq!(overlapping.buffer, front_only.buffer);
+    let config = DrawConfig {
+        depth_write: false,
+        ..DrawConfig::default()
+    };
+    let overlapping = draw(&gpu::TRANSLUCENT_SHAPES, config);
+    assert_ne!(overlapping.buffer, front_only.buffer);
+    assert_same_texture("blending#translucent", &overlapping);
+}
+
+#[ragna::gpu]
+mod gpu {
+    use ragna::{Array, F32x2, F32x4, Shape, Texture};
+
+    pub(super) static LAYERS: Texture = Texture::none();
+    pub(super) static FRONT_SHAPES: Array<Shape, 1> = [Shape {
+        position: F32x2::new(2., 2.),
+        size: F32x2::new(4., 4.),
+        rotation: 0.,
+        color: F32x4::new(1., 0., 0., 1.),
+        z: 0.8,
+    }];
+    pub(super) static BACK_SHAPES: Array<Shape, 1> = [Shape {
+        position: F32x2::new(2., 2.),
+        size: F32x2::new(4., 4.),
+        rotation: 0.,
+        color: F32x4::new(0., 1., 0., 0.5),
+        z: 0.2,
+    }];
+    pub(super) static TRANSLUCENT_FRONT_SHAPES: Array<Shape, 2> = [
+        Shape {
+            position: F32x2::new(2., 2.),
+            size: F32x2::new(4., 4.),
+            rotation: 0.,
+            color: F32x4::new(1., 0., 0., 0.5),
+            z: 0.8,
+        },
+        Shape {
+            position: F32x2::new(2., 2.),
+            size: F32x2::new(0., 0.),
+            rotation: 0.,
+            color: F32x4::new(0., 1., 0., 0.5),
+            z: 0.2,
+        },
+    ];
+    pub(super) static TRANSLUCENT_SHAPES: Array<Shape, 2> = [
+        Shape {
+            position: F32x2::new(2., 2.),
+            size: F32x2::new(4., 4.),
+            rotation: 0.,
+            color: F32x4::new(1., 0., 0., 0.5),
+            z: 0.8,
+        },
+        Shape {
+            position: F32x2::new(2., 2.),
+            size: F32x2::new(4., 4.),
+            rotation: 0.,
+            color: F32x4::new(0., 1., 0., 0.5),
+            z: 0.2,
+        },
+    ];
+}
@@ -0,0 +1,123 @@
+use ragna::{assert_same_texture, f32x2, App, InputEvent};
+
+#[test]
+pub fn convert_coordinates_with_default_camera() {
+    let app = App::default()
+        .with_module(gpu::register)
+        .texture((8, 4))
+        .run(1);
+    assert_eq!(
+        app.read(*gpu::DEFAULT_WORLD_TO_SCREEN),
+        Some(f32x2 { x: 2., y: 3. })
+    );
+    assert_eq!(
+        app.read(*gpu::DEFAULT_SCREEN_TO_NDC),
+        Some(f32x2 { x: -0.5, y: -0.5 })
+    );
+    assert_eq!(
+        app.read(*gpu::DEFAULT_NDC_TO_SCREEN),
+        Some(f32x2 { x: 4., y: 2. })
+    );
+    assert_eq!(
+        app.read(*gpu::DEFAULT_WORLD_TO_NDC),
+        Some(f32x2 { x: 0., y: 0. })
+    );
+}
+
+#[test]
+pub fn convert_coordinates_with_modified_camera() {
+    let app = App::default()
+        .with_module(gpu::register)
+        .texture((8, 4))
+        .inject_input(InputEvent::MouseMoved { x: 2., y: 4. })
+        .run(2);
+    assert_eq!(
+        app.read(*gpu::WORLD_TO_SCREEN),
+        Some(f32x2 { x: 6., y: 2. })
+    );
+    assert_eq!(
+        app.read(*gpu::SCREEN_TO_WORLD),
+        Some(f32x2 { x: 1., y: 1. })
+    );
+    assert_eq!(app.read(*gpu::NDC_TO_WORLD), Some(f32x2 { x: 2., y: 0. }));
+    assert_eq!(
+        app.read(*gpu::MOUSE_WORLD_POSITION),
+        Some(f32x2 { x: -1., y: 1. })
+    );
+    let rotated = app.read(*gpu::ROTATED_WORLD_TO_SCREEN).unwrap();
+    assert!((rotated.x - 4.).abs() < 1e-5);
+    assert!((rotated.y - 1.).abs() < 1e-5);
+}
+
+#[test]
+pub fn draw_with_camera() {
+    let app = App::default()
+        .with_module(gpu::register)
+        .with_shapes(&gpu::SHAPES)
+        .texture((8, 8))
+        .with_background_color((0., 0., 0., 1.))
+        .run(1);
+    assert_same_texture("camera#draw", &app.read_target());
+}
+
+#[ragna::gpu]
+mod gpu {
+    use ragna::{Array, F32x2, F32x4, Shape, F32};
+
+    pub(super) static DEFAULT_WORLD_TO_SCREEN: F32x2 = F32x2::new(0., 0.);
+    pub(super) static DEFAULT_SCREEN_TO_NDC: F32x2 = F32x2::new(0., 0.);
+    pub(super) static DEFAULT_NDC_TO_SCREEN: F32x2 = F32x2::new(0., 0.);
+    pub(super) static DEFAULT_WORLD_TO_NDC: F32x2 = F32x2::new(0., 0.);
+    pub(super) static WORLD_TO_SCREEN: F32x2 = F32x2::new(0., 0.);
+    pub(super) static SCREEN_TO_WORLD: F32x2 = F32x2::new(0., 0.);
+    pub(super) static NDC_TO_WORLD: F32x2 = F32x2::new(0., 0.);
+    pub(super) static MOUSE_WORLD_POSITION: F32x2 = F32x2::new(0., 0.);
+    pub(super) static ROTATED_WORLD_TO_SCREEN: F32x2 = F32x2::new(0., 0.);
+    pub(super) static SHAPES: Array<Shape, 2> = [
+        Shape {
+            position: F32x2::new(2., 2.),
+            size: F32x2::new(1., 1.),
+            rotation: 0.,
+            color: F32x4::new(1., 0., 0., 1.),
+            z: 0.,
+        },
+        Shape {
+            position: F32x2::new(4., 2.),
+            size: F32x2::new(1., 1.),
+            rotation: 0.,
+            color: F32x4::new(0., 1., 0., 1.),
+            z: 0.,
+        },
+    ];
+
+    #[compute]
+    fn update() {
+        if ragna::step_index() == 0u {
+            *DEFAULT_WORLD_TO_SCREEN = ragna::world_to_screen(F32x2::new(2., 3.));
+            *DEFAULT_SCREEN_TO_NDC = ragna::screen_to_ndc(F32x2::new(2., 3.));
+            *DEFAULT_NDC_TO_SCREEN = ragna::ndc_to_screen(F32x2::new(0., 0.));
+            *DEFAULT_WORLD_TO_NDC = ragna::world_to_ndc(F32x2::new(4., 2.));
+            ragna::camera().position = F32x2::new(2., 2.);
+            ragna::camera().zoom = 2.;
+            ragna::camera().viewport_size = F32x2::new(0.5, 1.);
+        } else {
+            ragna::camera().position = F32x2::new(0., 0.);
+            ragna::camera().viewport_size = F32x2::new(1., 1.);
+            *WORLD_TO_SCREEN = ragna::world_to_screen(F32x2::new(1., 0.));
+            *SCREEN_TO_WORLD = ragna::screen_to_world(F32x2::new(6., 4.));
+            *NDC_TO_WORLD = ragna::ndc_to_world(F32x2::new(1., 0.));
+            *MOUSE_WORLD_POSITION = ragna::mouse_world_position();
+            ragna::camera().zoom = 1.;
+            ragna::camera().rotation = rotation();
+            *ROTATED_WORLD_TO_SCREEN = ragna::world_to_screen(F32x2::new(1., 0.));
+        }
+    }
+
+    fn rotation() -> F32 {
+        atan2(1., 0.)
+    }
+
+    extern "wgsl" {
+        fn atan2(y: F32, x: F32) -> F32;
+    }
+}
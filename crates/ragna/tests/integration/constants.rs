@@ -1,4 +1,4 @@
-use ragna::App;
+use ragna::{f32x2, App, Cpu, F32x2, Wgsl};
 
 #[test]
 pub fn use_constant() {
@@ -9,13 +9,48 @@ pub fn use_constant() {
     assert_eq!(app.read(*gpu::CONSTANT_RES), Some(20));
 }
 
+#[test]
+pub fn use_constant_with_custom_cpu_type() {
+    let app = App::default()
+        .with_module(gpu::register)
+        .texture((1, 1))
+        .run(1);
+    assert_eq!(
+        app.read(*gpu::CUSTOM_CONSTANT_RES),
+        Some(f32x2 { x: 1.5, y: -2. })
+    );
+}
+
+#[derive(Clone, Copy)]
+struct Position(f32, f32);
+
+impl Cpu for Position {
+    type Gpu = F32x2;
+
+    fn from_gpu(bytes: &[u8]) -> Self {
+        let position = f32x2::from_gpu(bytes);
+        Self(position.x, position.y)
+    }
+
+    fn to_wgsl(&self) -> Wgsl {
+        f32x2 {
+            x: self.0,
+            y: self.1,
+        }
+        .to_wgsl()
+    }
+}
+
+const POSITION: Position = Position(1.5, -2.);
+
 #[ragna::gpu]
 mod gpu {
-    use ragna::{Cpu, I32};
+    use ragna::{Cpu, F32x2, I32};
 
     const CONSTANT: i32 = mul(2, 10);
 
     pub(super) static CONSTANT_RES: I32 = CONSTANT.to_gpu();
+    pub(super) static CUSTOM_CONSTANT_RES: F32x2 = super::POSITION.to_gpu();
 
     const fn mul(lhs: i32, rhs: i32) -> i32 {
         let left = lhs;
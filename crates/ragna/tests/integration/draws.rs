@@ -0,0 +1,82 @@
+use ragna::{assert_same_texture, App};
+
+#[test]
+pub fn draw_with_struct_vertex() {
+    let app = App::default()
+        .with_module(gpu::register)
+        .with_draw(gpu::vertex, gpu::fragment, 3, 2)
+        .texture((16, 16))
+        .run(1);
+    assert_same_texture("draws#struct_vertex", &app.read_target());
+}
+
+#[test]
+pub fn draw_with_position_vertex() {
+    let app = App::default()
+        .with_draw(gpu::position_vertex, gpu::position_fragment, 6, 1)
+        .texture((16, 16))
+        .run(1);
+    assert_same_texture("draws#position_vertex", &app.read_target());
+}
+
+#[ragna::gpu]
+mod gpu {
+    use ragna::{Array, F32x2, F32x4, F32, U32};
+
+    pub(super) static OFFSETS: Array<F32x2, 2> = [F32x2::new(-0.5, 0.), F32x2::new(0.5, 0.)];
+    pub(super) static BRIGHTNESS: F32 = 0.;
+
+    pub(super) struct Vertex {
+        pub(super) position: F32x4,
+        pub(super) color: F32x4,
+        pub(super) instance: U32,
+    }
+
+    #[compute]
+    fn update() {
+        *BRIGHTNESS = 1.;
+    }
+
+    #[vertex]
+    pub(super) fn vertex(vertex_index: U32, instance_index: U32) -> Vertex {
+        let corners: Array<F32x2, 3> = [
+            F32x2::new(0., 0.5),
+            F32x2::new(-0.4, -0.5),
+            F32x2::new(0.4, -0.5),
+        ];
+        let position = corners[vertex_index] + OFFSETS[instance_index];
+        Vertex {
+            position: F32x4::new(position.x, position.y, 0., 1.),
+            color: F32x4::new(1., 0., 0., 1.),
+            instance: instance_index,
+        }
+    }
+
+    #[fragment]
+    pub(super) fn fragment(vertex: Vertex) -> F32x4 {
+        let color = vertex.color;
+        if vertex.instance == 1u {
+            color = F32x4::new(0., *BRIGHTNESS, 0., 1.);
+        }
+        color
+    }
+
+    #[vertex]
+    pub(super) fn position_vertex(vertex_index: U32, instance_index: U32) -> F32x4 {
+        let corners: Array<F32x2, 6> = [
+            F32x2::new(-0.5, -0.5),
+            F32x2::new(0.5, -0.5),
+            F32x2::new(-0.5, 0.5),
+            F32x2::new(-0.5, 0.5),
+            F32x2::new(0.5, -0.5),
+            F32x2::new(0.5, 0.5),
+        ];
+        let corner = corners[vertex_index];
+        F32x4::new(corner.x, corner.y, 0., 1.)
+    }
+
+    #[fragment]
+    pub(super) fn position_fragment(position: F32x4) -> F32x4 {
+        F32x4::new(position.x / 16., position.y / 16., 1., 1.)
+    }
+}
@@ -1,4 +1,4 @@
-use ragna::App;
+use ragna::{f32x3, App, GpuConfig, Limits, RagnaError};
 
 #[test]
 pub fn use_globs() {
@@ -10,12 +10,146 @@ pub fn use_globs() {
     assert_eq!(app.read(*gpu::FROM_GLOB), Some(10));
 }
 
+static LEVEL_DATA: [u32; 50_000] = [7; 50_000];
+
+#[test]
+pub fn use_large_constant_globs() {
+    let app = App::default()
+        .with_module(gpu::register)
+        .texture((1, 1))
+        .run(1);
+    let level = app.read(*gpu::LEVEL).unwrap();
+    assert!(level.iter().all(|&tile| tile == 7));
+    assert_eq!(app.read(*gpu::LEVEL_TILE), Some(7));
+    assert_eq!(
+        app.read(*gpu::NESTED_LITERAL),
+        Some([[1, 2], [3, 4], [5, 6]])
+    );
+    assert_eq!(
+        app.read(*gpu::VECTORS),
+        Some([
+            f32x3 {
+                x: 1.,
+                y: 2.,
+                z: 3.
+            },
+            f32x3 {
+                x: 4.,
+                y: 5.,
+                z: 6.
+            }
+        ])
+    );
+    assert_eq!(app.read(*gpu::NEGATIVE_LITERAL), Some([-1, -2]));
+}
+
+#[test]
+pub fn use_large_constant_in_function() {
+    let app = App::default()
+        .with_module(fn_constant::register)
+        .texture((1, 1))
+        .run(1);
+    assert_eq!(app.read(*fn_constant::LEVEL_TILE), Some(7));
+    let result = App::default()
+        .with_module(fn_constant::register)
+        .with_gpu_config(GpuConfig {
+            required_limits: Limits {
+                max_storage_buffer_binding_size: 8,
+                ..Limits::default()
+            },
+            ..GpuConfig::default()
+        })
+        .try_texture((1, 1));
+    let Err(RagnaError::BufferTooLarge { size, .. }) = result else {
+        panic!("invalid result")
+    };
+    assert!(size > 4 * 50_000);
+}
+
+static TILE_DATA: [u32; 2_000] = [5; 2_000];
+
+#[test]
+pub fn use_same_large_constant_in_several_functions() {
+    let app = App::default()
+        .with_module(shared_constant::register)
+        .texture((1, 1))
+        .run(1);
+    assert_eq!(app.read(*shared_constant::TILE_SUM), Some(10));
+    let result = App::default()
+        .with_module(shared_constant::register)
+        .with_gpu_config(GpuConfig {
+            required_limits: Limits {
+                max_storage_buffer_binding_size: 8,
+                ..Limits::default()
+            },
+            ..GpuConfig::default()
+        })
+        .try_texture((1, 1));
+    let Err(RagnaError::BufferTooLarge { size, .. }) = result else {
+        panic!("invalid result")
+    };
+    assert!(size > 4 * 2_000);
+    assert!(size < 2 * 4 * 2_000);
+}
+
 #[ragna::gpu]
 mod gpu {
-    use ragna::{Cpu, I32};
+    use ragna::{f32x3, Array, Cpu, F32x3, I32, U32};
 
     const CONSTANT: i32 = 10;
+    const VECTORS_DATA: [f32x3; 2] = [
+        f32x3 {
+            x: 1.,
+            y: 2.,
+            z: 3.,
+        },
+        f32x3 {
+            x: 4.,
+            y: 5.,
+            z: 6.,
+        },
+    ];
 
     pub(super) static FROM_CONSTANT: I32 = CONSTANT.to_gpu();
     pub(super) static FROM_GLOB: I32 = *FROM_CONSTANT;
+    pub(super) static LEVEL: Array<U32, 50_000> = super::LEVEL_DATA.to_gpu();
+    pub(super) static LEVEL_TILE: U32 = LEVEL[12u];
+    pub(super) static NESTED_LITERAL: Array<Array<U32, 2>, 3> = [[1u, 2u], [3u, 4u], [5u, 6u]];
+    pub(super) static VECTORS: Array<F32x3, 2> = VECTORS_DATA.to_gpu();
+    pub(super) static NEGATIVE_LITERAL: Array<I32, 2> = [-1, -2];
+}
+
+#[ragna::gpu]
+mod fn_constant {
+    use ragna::{Cpu, U32};
+
+    pub(super) static LEVEL_TILE: U32 = 0u;
+
+    #[compute]
+    fn copy_level_tile() {
+        let level = super::LEVEL_DATA.to_gpu();
+        *LEVEL_TILE = level[12u];
+    }
+}
+
+#[ragna::gpu]
+mod shared_constant {
+    use ragna::{Cpu, U32};
+
+    pub(super) static TILE_SUM: U32 = 0u;
+
+    #[compute]
+    fn add_first_tile() {
+        *TILE_SUM += tile(0u);
+    }
+
+    #[compute]
+    fn add_last_tile() {
+        *TILE_SUM += tile(1_999u);
+    }
+
+    fn tile(index: U32) -> U32 {
+        let tiles = super::TILE_DATA.to_gpu();
+        tiles[index]
+    }
 }
@@ -0,0 +1,108 @@
+use ragna::{App, InputEvent, KeyCode, MouseButton, TextureApp};
+
+const UNSUPPORTED_BUTTON: MouseButton = MouseButton::Other(1000);
+
+#[test]
+pub fn use_injected_key_events() {
+    let app = App::default()
+        .with_module(gpu::register)
+        .texture((1, 1))
+        .inject_input(InputEvent::KeyPressed(KeyCode::Space))
+        .run(1);
+    assert_key_state(&app, (true, true, false));
+    let app = app.run(1);
+    assert_key_state(&app, (true, false, false));
+    let app = app
+        .inject_input(InputEvent::KeyPressed(KeyCode::Space))
+        .run(1);
+    assert_key_state(&app, (true, false, false));
+    let app = app
+        .inject_input(InputEvent::KeyReleased(KeyCode::Space))
+        .run(1);
+    assert_key_state(&app, (false, false, true));
+    let app = app
+        .inject_input(InputEvent::KeyPressed(KeyCode::KeyA))
+        .run(1);
+    assert_key_state(&app, (false, false, false));
+    let app = app
+        .inject_input(InputEvent::KeyPressed(KeyCode::Space))
+        .inject_input(InputEvent::KeyReleased(KeyCode::Space))
+        .run(1);
+    assert_key_state(&app, (false, true, true));
+}
+
+#[test]
+pub fn use_injected_mouse_events() {
+    let app = App::default()
+        .with_module(gpu::register)
+        .texture((1, 1))
+        .inject_input(InputEvent::MouseButtonPressed(MouseButton::Left))
+        .inject_input(InputEvent::MouseMoved { x: 10., y: 20. })
+        .inject_input(InputEvent::MouseWheelScrolled { x: 1., y: 2. })
+        .inject_input(InputEvent::MouseWheelScrolled { x: 0., y: 3. })
+        .run(1);
+    assert_mouse_button_state(&app, (true, true, false));
+    assert_eq!(app.read(*gpu::MOUSE_X), Some(10.));
+    assert_eq!(app.read(*gpu::MOUSE_Y), Some(20.));
+    assert_eq!(app.read(*gpu::WHEEL_X), Some(1.));
+    assert_eq!(app.read(*gpu::WHEEL_Y), Some(5.));
+    let app = app
+        .inject_input(InputEvent::MouseButtonReleased(MouseButton::Left))
+        .run(1);
+    assert_mouse_button_state(&app, (false, false, true));
+    assert_eq!(app.read(*gpu::MOUSE_X), Some(10.));
+    assert_eq!(app.read(*gpu::MOUSE_Y), Some(20.));
+    assert_eq!(app.read(*gpu::WHEEL_X), Some(0.));
+    assert_eq!(app.read(*gpu::WHEEL_Y), Some(0.));
+    let app = app
+        .inject_input(InputEvent::MouseButtonPressed(UNSUPPORTED_BUTTON))
+        .run(1);
+    assert_mouse_button_state(&app, (false, false, false));
+    assert_eq!(app.read(*gpu::UNSUPPORTED_BUTTON_PRESSED), Some(false));
+}
+
+fn assert_key_state(app: &TextureApp, expected: (bool, bool, bool)) {
+    assert_eq!(app.read(*gpu::KEY_PRESSED), Some(expected.0));
+    assert_eq!(app.read(*gpu::KEY_JUST_PRESSED), Some(expected.1));
+    assert_eq!(app.read(*gpu::KEY_JUST_RELEASED), Some(expected.2));
+}
+
+fn assert_mouse_button_state(app: &TextureApp, expected: (bool, bool, bool)) {
+    assert_eq!(app.read(*gpu::BUTTON_PRESSED), Some(expected.0));
+    assert_eq!(app.read(*gpu::BUTTON_JUST_PRESSED), Some(expected.1));
+    assert_eq!(app.read(*gpu::BUTTON_JUST_RELEASED), Some(expected.2));
+}
+
+#[ragna::gpu]
+mod gpu {
+    use ragna::{Bool, KeyCode, MouseButton, F32};
+
+    pub(super) static KEY_PRESSED: Bool = false;
+    pub(super) static KEY_JUST_PRESSED: Bool = false;
+    pub(super) static KEY_JUST_RELEASED: Bool = false;
+    pub(super) static BUTTON_PRESSED: Bool = false;
+    pub(super) static BUTTON_JUST_PRESSED: Bool = false;
+    pub(super) static BUTTON_JUST_RELEASED: Bool = false;
+    pub(super) static UNSUPPORTED_BUTTON_PRESSED: Bool = false;
+    pub(super) static MOUSE_X: F32 = 0.;
+    pub(super) static MOUSE_Y: F32 = 0.;
+    pub(super) static WHEEL_X: F32 = 0.;
+    pub(super) static WHEEL_Y: F32 = 0.;
+
+    #[compute]
+    fn run() {
+        *KEY_PRESSED = ragna::is_key_pressed(KeyCode::Space);
+        *KEY_JUST_PRESSED = ragna::is_key_just_pressed(KeyCode::Space);
+        *KEY_JUST_RELEASED = ragna::is_key_just_released(KeyCode::Space);
+        *BUTTON_PRESSED = ragna::is_mouse_button_pressed(MouseButton::Left);
+        *BUTTON_JUST_PRESSED = ragna::is_mouse_button_just_pressed(MouseButton::Left);
+        *BUTTON_JUST_RELEASED = ragna::is_mouse_button_just_released(MouseButton::Left);
+        *UNSUPPORTED_BUTTON_PRESSED = ragna::is_mouse_button_pressed(super::UNSUPPORTED_BUTTON);
+        let position = ragna::mouse_position();
+        *MOUSE_X = position.x;
+        *MOUSE_Y = position.y;
+        let wheel_delta = ragna::mouse_wheel_delta();
+        *WHEEL_X = wheel_delta.x;
+        *WHEEL_Y = wheel_delta.y;
+    }
+}
@@ -2,15 +2,27 @@
 
 pub mod app;
 pub mod assignment;
+pub mod blending;
+pub mod camera;
 pub mod constants;
+pub mod draws;
 pub mod fns;
 pub mod globs;
 pub mod if_;
 pub mod imports;
+pub mod input;
 pub mod loops;
+pub mod offscreen_targets;
 pub mod operators;
+pub mod pixels;
+pub mod post_process;
 pub mod references;
+pub mod shapes;
+pub mod sprites;
 pub mod structs;
 pub mod target;
 pub mod testing;
+pub mod text;
+pub mod textures;
+pub mod time;
 pub mod types;
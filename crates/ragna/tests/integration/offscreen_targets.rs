@@ -0,0 +1,113 @@
+use ragna::{assert_same_texture, App, SamplerConfig, TextureTargetConfig, TextureTargetFormat};
+
+#[test]
+pub fn render_offscreen_targets() {
+    let app = App::default()
+        .with_module(gpu::register)
+        .with_offscreen_target(
+            "minimap",
+            &gpu::MINIMAP,
+            (4, 4),
+            SamplerConfig::default(),
+            |app| {
+                app.with_shapes(&gpu::SHAPES)
+                    .with_sprites(&gpu::SELF_SPRITES)
+            },
+        )
+        .with_offscreen_target(
+            "overlay",
+            &gpu::OVERLAY,
+            (8, 8),
+            SamplerConfig::default(),
+            |app| app.with_sprites(&gpu::MINIMAP_SPRITES),
+        )
+        .with_sprites(&gpu::OVERLAY_SPRITES)
+        .texture((8, 8))
+        .with_background_color((0., 0., 1., 1.))
+        .run(1);
+    assert_same_texture("offscreen_targets#minimap", &app.read_texture("minimap"));
+    assert_same_texture("offscreen_targets#overlay", &app.read_texture("overlay"));
+    assert_same_texture("offscreen_targets#target", &app.read_target());
+}
+
+#[test]
+pub fn read_offscreen_target_with_other_format_than_main_target() {
+    let config = TextureTargetConfig {
+        format: TextureTargetFormat::Rgba16Float,
+        ..TextureTargetConfig::default()
+    };
+    let app = App::default()
+        .with_module(gpu::register)
+        .with_offscreen_target(
+            "minimap",
+            &gpu::MINIMAP,
+            (4, 4),
+            SamplerConfig::default(),
+            |draws| draws.with_shapes(&gpu::SHAPES),
+        )
+        .texture_with((4, 4), config)
+        .run(1);
+    let texture = app.read_texture("minimap");
+    assert_eq!(texture.format, TextureTargetFormat::Rgba8UnormSrgb);
+    assert_eq!(texture.buffer.len(), 4 * 4 * 4);
+}
+
+#[test]
+#[should_panic = "no offscreen target `missing` registered"]
+pub fn read_missing_offscreen_target() {
+    let app = App::default().texture((1, 1));
+    app.read_texture("missing");
+}
+
+#[test]
+#[should_panic = "offscreen target `minimap` already registered"]
+pub fn register_offscreen_targets_with_same_name() {
+    let _app = App::default()
+        .with_offscreen_target(
+            "minimap",
+            &gpu::MINIMAP,
+            (4, 4),
+            SamplerConfig::default(),
+            |app| app,
+        )
+        .with_offscreen_target(
+            "minimap",
+            &gpu::OVERLAY,
+            (4, 4),
+            SamplerConfig::default(),
+            |app| app,
+        );
+}
+
+#[ragna::gpu]
+mod gpu {
+    use ragna::{Array, F32x2, F32x4, Shape, Sprite, Texture};
+
+    pub(super) static MINIMAP: Texture = Texture::none();
+    pub(super) static OVERLAY: Texture = Texture::none();
+    pub(super) static SHAPES: Array<Shape, 1> = [Shape {
+        position: F32x2::new(2., 4.),
+        size: F32x2::new(4., 8.),
+        rotation: 0.,
+        color: F32x4::new(1., 0., 0., 1.),
+        z: 0.,
+    }];
+    pub(super) static SELF_SPRITES: Array<Sprite, 1> = [sprite(*MINIMAP, F32x2::new(4., 4.))];
+    pub(super) static MINIMAP_SPRITES: Array<Sprite, 1> = [sprite(*MINIMAP, F32x2::new(4., 4.))];
+    pub(super) static OVERLAY_SPRITES: Array<Sprite, 1> = [sprite(*OVERLAY, F32x2::new(8., 8.))];
+
+    fn sprite(texture: Texture, region_size: F32x2) -> Sprite {
+        Sprite {
+            position: F32x2::new(6., 6.),
+            size: F32x2::new(4., 4.),
+            rotation: 0.,
+            texture,
+            region_position: F32x2::new(0., 0.),
+            region_size,
+            tint: F32x4::new(1., 1., 1., 1.),
+            flip_x: false,
+            flip_y: false,
+            z: 0.,
+        }
+    }
+}
@@ -0,0 +1,50 @@
+use ragna::{assert_same_texture, App};
+
+#[test]
+pub fn set_pixels() {
+    let app = App::default()
+        .with_module(gpu::register)
+        .texture((4, 4))
+        .with_background_color((0., 0., 0., 1.))
+        .run(1);
+    assert_same_texture("pixels#first_step", &app.read_target());
+    let app = app.run(1);
+    assert_same_texture("pixels#second_step", &app.read_target());
+}
+
+#[test]
+pub fn set_pixels_behind_shapes() {
+    let app = App::default()
+        .with_module(gpu::register)
+        .with_shapes(&gpu::SHAPES)
+        .texture((4, 4))
+        .with_background_color((0., 0., 0., 1.))
+        .run(1);
+    assert_same_texture("pixels#behind_shapes", &app.read_target());
+}
+
+#[ragna::gpu]
+mod gpu {
+    use ragna::{Array, F32x2, F32x4, Shape, U32x2};
+
+    pub(super) static SHAPES: Array<Shape, 1> = [Shape {
+        position: F32x2::new(3., 3.),
+        size: F32x2::new(2., 2.),
+        rotation: 0.,
+        color: F32x4::new(0., 0., 1., 1.),
+        z: 0.,
+    }];
+
+    #[compute]
+    fn update() {
+        if ragna::step_index() == 0u {
+            ragna::set_pixel(U32x2::new(0u, 0u), F32x4::new(1., 0., 0., 1.));
+            ragna::set_pixel(U32x2::new(10u, 10u), F32x4::new(1., 1., 1., 1.));
+        } else {
+            ragna::set_pixel(U32x2::new(1u, 2u), F32x4::new(0., 1., 0., 0.5));
+        }
+        for x in 0u..4u {
+            ragna::set_pixel(U32x2::new(x, 3u), F32x4::new(1., 1., 0., 1.));
+        }
+    }
+}
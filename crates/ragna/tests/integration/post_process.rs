@@ -0,0 +1,73 @@
+use ragna::{assert_same_texture, App};
+
+#[test]
+pub fn apply_post_processes() {
+    let app = App::default()
+        .with_module(gpu::register)
+        .with_shapes(&gpu::SHAPES)
+        .with_post_process(gpu::grayscale)
+        .with_post_process(gpu::invert)
+        .texture((4, 4))
+        .with_background_color((0., 0., 1., 1.))
+        .run(1);
+    assert_same_texture("post_process#scene", &app.read_post_process_source(0));
+    assert_same_texture("post_process#grayscale", &app.read_post_process_source(1));
+    assert_same_texture("post_process#target", &app.read_target());
+}
+
+#[test]
+pub fn apply_builtin_post_processes() {
+    let app = App::default()
+        .with_module(gpu::register)
+        .with_shapes(&gpu::SHAPES)
+        .with_post_process(gpu::blurred)
+        .with_post_process(gpu::vignetted)
+        .texture((8, 8))
+        .with_background_color((0., 0., 1., 1.))
+        .run(1);
+    assert_same_texture("post_process#builtins", &app.read_target());
+}
+
+#[test]
+#[should_panic = "no post-process registered at this index"]
+pub fn read_missing_post_process_source() {
+    let app = App::default()
+        .with_post_process(gpu::invert)
+        .texture((4, 4));
+    app.read_post_process_source(1);
+}
+
+#[ragna::gpu]
+mod gpu {
+    use ragna::{Array, F32x2, F32x4, Shape};
+
+    pub(super) static SHAPES: Array<Shape, 1> = [Shape {
+        position: F32x2::new(1., 2.),
+        size: F32x2::new(2., 4.),
+        rotation: 0.,
+        color: F32x4::new(1., 0., 0., 1.),
+        z: 0.,
+    }];
+
+    #[fragment]
+    pub(super) fn grayscale(position: F32x4) -> F32x4 {
+        let color = ragna::source_color(F32x2::new(position.x, position.y));
+        ragna::grade_color(color, 0., 1., 0.)
+    }
+
+    #[fragment]
+    pub(super) fn invert(position: F32x4) -> F32x4 {
+        let color = ragna::source_color(F32x2::new(position.x, position.y));
+        F32x4::new(1. - color.x, 1. - color.y, 1. - color.z, color.w)
+    }
+
+    #[fragment]
+    pub(super) fn blurred(position: F32x4) -> F32x4 {
+        ragna::blur(F32x2::new(position.x, position.y), 1.)
+    }
+
+    #[fragment]
+    pub(super) fn vignetted(position: F32x4) -> F32x4 {
+        ragna::vignette(F32x2::new(position.x, position.y), 0.5)
+    }
+}
@@ -0,0 +1,56 @@
+use ragna::{assert_same_texture, App};
+
+#[test]
+pub fn draw_shapes() {
+    let app = App::default()
+        .with_module(gpu::register)
+        .with_shapes(&gpu::SHAPES)
+        .texture((16, 16))
+        .with_background_color((0., 0., 0., 1.))
+        .run(1);
+    assert_same_texture("shapes#draw", &app.read_target());
+}
+
+#[test]
+pub fn draw_shapes_without_registered_glob() {
+    let app = App::default()
+        .with_shapes(&gpu::SHAPES)
+        .texture((16, 16))
+        .with_background_color((0., 0., 0., 1.))
+        .run(1);
+    assert_same_texture("shapes#not_updated", &app.read_target());
+}
+
+#[ragna::gpu]
+mod gpu {
+    use ragna::{Array, F32x2, F32x4, Shape};
+
+    pub(super) static SHAPES: Array<Shape, 3> = [
+        Shape {
+            position: F32x2::new(4., 4.),
+            size: F32x2::new(6., 6.),
+            rotation: 0.,
+            color: F32x4::new(1., 0., 0., 1.),
+            z: 0.5,
+        },
+        Shape {
+            position: F32x2::new(8., 8.),
+            size: F32x2::new(6., 6.),
+            rotation: 0.,
+            color: F32x4::new(0., 1., 0., 1.),
+            z: 0.2,
+        },
+        Shape {
+            position: F32x2::new(12., 10.),
+            size: F32x2::new(6., 10.),
+            rotation: 0.,
+            color: F32x4::new(0., 0., 1., 0.5),
+            z: 0.8,
+        },
+    ];
+
+    #[compute]
+    fn update() {
+        SHAPES[1u].rotation = 0.8;
+    }
+}
@@ -0,0 +1,100 @@
+use ragna::{assert_same_texture, App, Image, SamplerConfig};
+
+#[test]
+pub fn draw_sprites() {
+    let app = App::default()
+        .with_module(gpu::register)
+        .with_texture(&gpu::ATLAS, atlas(), SamplerConfig::default())
+        .with_sprites(&gpu::SPRITES)
+        .texture((16, 16))
+        .with_background_color((0., 0., 0., 1.))
+        .run(1);
+    assert_same_texture("sprites#draw", &app.read_target());
+}
+
+fn atlas() -> Image {
+    let red = [255, 0, 0, 255];
+    let green = [0, 255, 0, 255];
+    let blue = [0, 0, 255, 255];
+    let white = [255, 255, 255, 255];
+    let yellow = [255, 255, 0, 255];
+    let transparent = [0, 0, 0, 0];
+    Image::from_rgba(
+        [
+            red,
+            green,
+            yellow,
+            yellow,
+            blue,
+            white,
+            transparent,
+            transparent,
+        ]
+        .concat(),
+        (4, 2),
+    )
+}
+
+#[ragna::gpu]
+mod gpu {
+    use ragna::{Array, F32x2, F32x4, Sprite, Texture};
+
+    pub(super) static ATLAS: Texture = Texture::none();
+    pub(super) static SPRITES: Array<Sprite, 4> = [
+        Sprite {
+            position: F32x2::new(4., 4.),
+            size: F32x2::new(8., 8.),
+            rotation: 0.,
+            texture: Texture::none(),
+            region_position: F32x2::new(0., 0.),
+            region_size: F32x2::new(2., 2.),
+            tint: F32x4::new(1., 1., 1., 1.),
+            flip_x: false,
+            flip_y: false,
+            z: 0.5,
+        },
+        Sprite {
+            position: F32x2::new(10., 10.),
+            size: F32x2::new(8., 8.),
+            rotation: 0.,
+            texture: Texture::none(),
+            region_position: F32x2::new(0., 0.),
+            region_size: F32x2::new(2., 2.),
+            tint: F32x4::new(1., 1., 1., 1.),
+            flip_x: true,
+            flip_y: true,
+            z: 0.8,
+        },
+        Sprite {
+            position: F32x2::new(8., 8.),
+            size: F32x2::new(8., 8.),
+            rotation: 0.,
+            texture: Texture::none(),
+            region_position: F32x2::new(2., 0.),
+            region_size: F32x2::new(2., 2.),
+            tint: F32x4::new(1., 0., 1., 1.),
+            flip_x: false,
+            flip_y: false,
+            z: 0.9,
+        },
+        Sprite {
+            position: F32x2::new(8., 8.),
+            size: F32x2::new(16., 16.),
+            rotation: 0.,
+            texture: Texture::none(),
+            region_position: F32x2::new(0., 0.),
+            region_size: F32x2::new(2., 2.),
+            tint: F32x4::new(1., 1., 1., 1.),
+            flip_x: false,
+            flip_y: false,
+            z: 1.,
+        },
+    ];
+
+    #[compute]
+    fn update() {
+        SPRITES[0u].texture = *ATLAS;
+        SPRITES[1u].texture = *ATLAS;
+        SPRITES[2u].texture = *ATLAS;
+    }
+}
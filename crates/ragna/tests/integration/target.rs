@@ -1,4 +1,5 @@
-use ragna::{assert_same_texture, App};
+use futures::executor;
+use ragna::{assert_same_texture, App, TextureTargetConfig, TextureTargetFormat};
 
 #[test]
 pub fn read_not_rendered_target() {
@@ -12,6 +13,17 @@ pub fn read_rendered_target() {
     assert_same_texture("target#default_rendered", &app.read_target());
 }
 
+#[test]
+pub fn read_target_async() {
+    let app = App::default()
+        .texture((4, 4))
+        .with_background_color((0., 0., 1., 1.))
+        .run(1);
+    let target = executor::block_on(app.read_target_async());
+    assert_eq!(target.size, (4, 4));
+    assert_same_texture("target#blue_background", &target);
+}
+
 #[test]
 pub fn configure_background_color() {
     let app = App::default()
@@ -20,3 +32,134 @@ pub fn configure_background_color() {
         .run(1);
     assert_same_texture("target#blue_background", &app.read_target());
 }
+
+#[test]
+pub fn use_target_properties() {
+    let app = App::default()
+        .with_module(gpu::register)
+        .texture((4, 3))
+        .run(1);
+    assert_eq!(app.read(*gpu::WIDTH), Some(4));
+    assert_eq!(app.read(*gpu::HEIGHT), Some(3));
+    assert_eq!(app.read(*gpu::SCALE_FACTOR), Some(1.));
+}
+
+#[test]
+pub fn configure_hdr_format() {
+    let config = TextureTargetConfig {
+        format: TextureTargetFormat::Rgba16Float,
+        ..TextureTargetConfig::default()
+    };
+    let app = App::default()
+        .with_module(gpu::register)
+        .with_shapes(&gpu::HDR_SHAPES)
+        .texture_with((4, 4), config)
+        .run(1);
+    let target = app.read_target();
+    assert_eq!(target.format, TextureTargetFormat::Rgba16Float);
+    assert_eq!(
+        target.buffer.len(),
+        4 * 4 * TextureTargetFormat::Rgba16Float.pixel_size()
+    );
+    let red = u16::from_le_bytes([target.buffer[0], target.buffer[1]]);
+    let green = u16::from_le_bytes([target.buffer[2], target.buffer[3]]);
+    assert_eq!(red, 0x4000); // 2.0 in half precision
+    assert_eq!(green, 0x3800); // 0.5 in half precision
+    assert_eq!(
+        target.to_rgba8().format,
+        TextureTargetFormat::Rgba8UnormSrgb
+    );
+    assert_same_texture("target#hdr", &target);
+}
+
+#[test]
+pub fn configure_unorm_format() {
+    let read_gray_target = |format| {
+        let config = TextureTargetConfig {
+            format,
+            ..TextureTargetConfig::default()
+        };
+        App::default()
+            .texture_with((4, 4), config)
+            .with_background_color((0.5, 0.5, 0.5, 1.))
+            .run(1)
+            .read_target()
+    };
+    let target = read_gray_target(TextureTargetFormat::Rgba8Unorm);
+    assert_eq!(target.format, TextureTargetFormat::Rgba8Unorm);
+    assert_eq!(target.buffer[..4], [128, 128, 128, 255]);
+    let srgb_target = read_gray_target(TextureTargetFormat::Rgba8UnormSrgb);
+    assert_eq!(target.to_rgba8().buffer, srgb_target.buffer);
+}
+
+#[test]
+pub fn configure_multisampling() {
+    let config = TextureTargetConfig {
+        samples: 4,
+        ..TextureTargetConfig::default()
+    };
+    let app = App::default()
+        .with_module(gpu::register)
+        .with_shapes(&gpu::ROTATED_SHAPES)
+        .texture_with((8, 8), config)
+        .with_background_color((0., 0., 0., 1.))
+        .run(1);
+    let target = app.read_target();
+    assert!(target
+        .buffer
+        .iter()
+        .any(|&component| component > 0 && component < 255));
+    assert_same_texture("target#multisampling", &target);
+}
+
+#[test]
+pub fn configure_multisampling_with_post_process() {
+    let config = TextureTargetConfig {
+        samples: 4,
+        ..TextureTargetConfig::default()
+    };
+    let app = App::default()
+        .with_module(gpu::register)
+        .with_shapes(&gpu::ROTATED_SHAPES)
+        .with_post_process(gpu::copy)
+        .texture_with((8, 8), config)
+        .with_background_color((0., 0., 0., 1.))
+        .run(1);
+    assert_same_texture("target#multisampling", &app.read_target());
+}
+
+#[ragna::gpu]
+mod gpu {
+    use ragna::{Array, F32x2, F32x4, Shape, F32, U32};
+
+    pub(super) static WIDTH: U32 = 0u;
+    pub(super) static HEIGHT: U32 = 0u;
+    pub(super) static SCALE_FACTOR: F32 = 0.;
+    pub(super) static HDR_SHAPES: Array<Shape, 1> = [Shape {
+        position: F32x2::new(2., 2.),
+        size: F32x2::new(4., 4.),
+        rotation: 0.,
+        color: F32x4::new(2., 0.5, 0., 1.),
+        z: 0.,
+    }];
+    pub(super) static ROTATED_SHAPES: Array<Shape, 1> = [Shape {
+        position: F32x2::new(4., 4.),
+        size: F32x2::new(5., 5.),
+        rotation: 0.5,
+        color: F32x4::new(1., 1., 1., 1.),
+        z: 0.,
+    }];
+
+    #[fragment]
+    pub(super) fn copy(position: F32x4) -> F32x4 {
+        ragna::source_color(F32x2::new(position.x, position.y))
+    }
+
+    #[compute]
+    fn run() {
+        let size = ragna::target_size();
+        *WIDTH = size.x;
+        *HEIGHT = size.y;
+        *SCALE_FACTOR = ragna::scale_factor();
+    }
+}
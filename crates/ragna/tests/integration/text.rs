@@ -0,0 +1,62 @@
+use ragna::{assert_same_texture, App, Image, SamplerConfig};
+
+#[test]
+pub fn draw_text() {
+    let app = App::default()
+        .with_module(gpu::register)
+        .with_texture(&gpu::FONT, font(), SamplerConfig::default())
+        .with_text(&gpu::TEXT)
+        .texture((16, 4))
+        .with_background_color((0., 0., 0., 1.))
+        .run(1);
+    let text = app.read(*gpu::TEXT).unwrap();
+    assert_eq!(text.length, 6);
+    assert_eq!(text.chars, [32, 49, 58, 51, 48, 53]);
+    assert_same_texture("text#draw", &app.read_target());
+}
+
+// 2x2 glyphs for digits from '0' to '9', in 2 rows of 5 glyphs
+fn font() -> Image {
+    let buffer = (0_u8..4)
+        .flat_map(|y| (0_u8..10).map(move |x| (x, y)))
+        .flat_map(|(x, y)| {
+            let digit = x.div_euclid(2) + y.div_euclid(2) * 5;
+            if x % 2 == 1 && y % 2 == 1 {
+                [0, 0, 0, 0]
+            } else {
+                [digit * 25, 255 - digit * 25, 255, 255]
+            }
+        })
+        .collect();
+    Image::from_rgba(buffer, (10, 4))
+}
+
+#[ragna::gpu]
+mod gpu {
+    use ragna::{F32x2, F32x4, Font, Text, Texture};
+
+    pub(super) static FONT: Texture = Texture::none();
+    pub(super) static TEXT: Text<6> = Text::<6> {
+        position: F32x2::new(2., 1.),
+        char_size: F32x2::new(2., 2.),
+        color: F32x4::new(1., 1., 1., 1.),
+        font: Font {
+            texture: Texture::none(),
+            glyph_size: F32x2::new(2., 2.),
+            columns: 5u,
+            first_char: 48u,
+        },
+        chars: [0u, 0u, 0u, 0u, 0u, 0u],
+        length: 0u,
+        z: 0.,
+    };
+
+    #[compute]
+    fn update() {
+        TEXT.font.texture = *FONT;
+        TEXT.clear();
+        TEXT.push_str(" 1:");
+        TEXT.push_number(305u);
+        TEXT.push_char(57u);
+    }
+}
@@ -0,0 +1,178 @@
+use ragna::{
+    assert_same_texture, f32x4, u32x2, App, Image, RagnaError, SamplerConfig, TextureFilter,
+    TextureWrap,
+};
+
+#[test]
+pub fn sample_texture_in_compute_function() {
+    let app = App::default()
+        .with_module(gpu::register)
+        .with_texture(&gpu::TEXTURE, image(), SamplerConfig::default())
+        .texture((1, 1))
+        .run(1);
+    assert_eq!(app.read(*gpu::TOP_LEFT), Some(color(1., 0., 0., 1.)));
+    assert_eq!(app.read(*gpu::TOP_RIGHT), Some(color(0., 1., 0., 1.)));
+    assert_eq!(app.read(*gpu::BOTTOM_LEFT), Some(color(0., 0., 1., 1.)));
+    assert_eq!(app.read(*gpu::OUTSIDE), Some(color(1., 1., 1., 1.)));
+    assert_eq!(app.read(*gpu::UNSET_COLOR), Some(color(0., 0., 0., 0.)));
+    assert_eq!(app.read(*gpu::SIZE), Some(u32x2 { x: 2, y: 2 }));
+    assert_eq!(app.read(*gpu::UNSET_SIZE), Some(u32x2 { x: 0, y: 0 }));
+}
+
+#[test]
+pub fn sample_texture_with_repeat_wrap() {
+    let sampler = SamplerConfig {
+        wrap: TextureWrap::Repeat,
+        ..SamplerConfig::default()
+    };
+    let app = App::default()
+        .with_module(gpu::register)
+        .with_texture(&gpu::TEXTURE, image(), sampler)
+        .texture((1, 1))
+        .run(1);
+    assert_eq!(app.read(*gpu::OUTSIDE), Some(color(1., 0., 0., 1.)));
+}
+
+#[test]
+pub fn sample_texture_in_fragment_function() {
+    let sampler = SamplerConfig {
+        filter: TextureFilter::Linear,
+        ..SamplerConfig::default()
+    };
+    let app = App::default()
+        .with_texture(&gpu::OTHER_TEXTURE, image(), SamplerConfig::default())
+        .with_texture(&gpu::TEXTURE, image(), sampler)
+        .with_draw(gpu::vertex, gpu::fragment, 6, 1)
+        .texture((8, 8))
+        .run(1);
+    assert_same_texture("textures#fragment", &app.read_target());
+}
+
+#[test]
+pub fn load_too_many_textures() {
+    let app_with_textures = |count| {
+        (0..count).fold(App::default(), |app, _| {
+            app.with_texture(&gpu::TEXTURE, image(), SamplerConfig::default())
+        })
+    };
+    let error = Some(RagnaError::TooManyTextures {
+        count: 17,
+        max_count: 16,
+    });
+    assert!(app_with_textures(16).try_texture((1, 1)).is_ok());
+    assert_eq!(app_with_textures(17).try_texture((1, 1)).err(), error);
+    let result = app_with_textures(16)
+        .with_post_process(gpu::source)
+        .try_texture((1, 1));
+    assert_eq!(result.err(), error);
+}
+
+#[test]
+pub fn read_texture_handle() {
+    let app = App::default()
+        .with_texture(&gpu::OTHER_TEXTURE, image(), SamplerConfig::default())
+        .with_texture(&gpu::TEXTURE, image(), SamplerConfig::default())
+        .with_shapes(&gpu::SHAPES)
+        .texture((1, 1));
+    assert!(app.read(*gpu::TEXTURE).is_some());
+    assert!(app.read(*gpu::UNSET).is_none());
+}
+
+#[test]
+pub fn load_image_from_file() {
+    let path = concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/tests/expected/pixels#first_step.png"
+    );
+    let image = Image::from_file(path).unwrap();
+    assert_eq!(image.size(), (4, 4));
+}
+
+#[test]
+pub fn load_missing_image_file() {
+    let result = Image::from_file("missing.png");
+    assert!(matches!(result, Err(RagnaError::ImageLoading { .. })));
+}
+
+#[test]
+#[should_panic = "image buffer length doesn't correspond to image size"]
+pub fn create_image_with_invalid_buffer() {
+    Image::from_rgba(vec![0; 12], (2, 2));
+}
+
+fn image() -> Image {
+    Image::from_rgba(
+        [
+            [255, 0, 0, 255],
+            [0, 255, 0, 255],
+            [0, 0, 255, 255],
+            [255, 255, 255, 255],
+        ]
+        .concat(),
+        (2, 2),
+    )
+}
+
+fn color(x: f32, y: f32, z: f32, w: f32) -> f32x4 {
+    f32x4 { x, y, z, w }
+}
+
+#[ragna::gpu]
+mod gpu {
+    use ragna::{Array, F32x2, F32x4, Shape, Texture, U32x2, U32};
+
+    pub(super) static SHAPES: Array<Shape, 1> = [Shape {
+        position: F32x2::new(0., 0.),
+        size: F32x2::new(1., 1.),
+        rotation: 0.,
+        color: F32x4::new(1., 1., 1., 1.),
+        z: 0.,
+    }];
+
+    pub(super) static TEXTURE: Texture = Texture::none();
+    pub(super) static OTHER_TEXTURE: Texture = Texture::none();
+    pub(super) static UNSET: Texture = Texture::none();
+    pub(super) static TOP_LEFT: F32x4 = F32x4::new(0., 0., 0., 0.);
+    pub(super) static TOP_RIGHT: F32x4 = F32x4::new(0., 0., 0., 0.);
+    pub(super) static BOTTOM_LEFT: F32x4 = F32x4::new(0., 0., 0., 0.);
+    pub(super) static OUTSIDE: F32x4 = F32x4::new(0., 0., 0., 0.);
+    pub(super) static UNSET_COLOR: F32x4 = F32x4::new(1., 1., 1., 1.);
+    pub(super) static SIZE: U32x2 = U32x2::new(0u, 0u);
+    pub(super) static UNSET_SIZE: U32x2 = U32x2::new(1u, 1u);
+
+    #[compute]
+    fn update() {
+        *TOP_LEFT = ragna::sample(*TEXTURE, F32x2::new(0.25, 0.25));
+        *TOP_RIGHT = ragna::sample(*TEXTURE, F32x2::new(0.75, 0.25));
+        *BOTTOM_LEFT = ragna::sample(*TEXTURE, F32x2::new(0.25, 0.75));
+        *OUTSIDE = ragna::sample(*TEXTURE, F32x2::new(1.25, 1.25));
+        *UNSET_COLOR = ragna::sample(*UNSET, F32x2::new(0.25, 0.25));
+        *SIZE = ragna::texture_size(*TEXTURE);
+        *UNSET_SIZE = ragna::texture_size(*UNSET);
+    }
+
+    #[vertex]
+    pub(super) fn vertex(vertex_index: U32, instance_index: U32) -> F32x4 {
+        let corners: Array<F32x2, 6> = [
+            F32x2::new(-1., -1.),
+            F32x2::new(1., -1.),
+            F32x2::new(-1., 1.),
+            F32x2::new(-1., 1.),
+            F32x2::new(1., -1.),
+            F32x2::new(1., 1.),
+        ];
+        let corner = corners[vertex_index];
+        F32x4::new(corner.x, corner.y, 0., 1.)
+    }
+
+    #[fragment]
+    pub(super) fn fragment(position: F32x4) -> F32x4 {
+        let uv = F32x2::new(position.x / 8., position.y / 8.);
+        ragna::sample(*TEXTURE, uv)
+    }
+
+    #[fragment]
+    pub(super) fn source(position: F32x4) -> F32x4 {
+        ragna::source_color(F32x2::new(position.x, position.y))
+    }
+}
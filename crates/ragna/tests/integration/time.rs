@@ -0,0 +1,49 @@
+use ragna::App;
+use std::time::Duration;
+
+#[test]
+pub fn use_time_with_fixed_delta() {
+    let app = App::default()
+        .with_module(gpu::register)
+        .texture((1, 1))
+        .with_fixed_delta(Duration::from_millis(500))
+        .run(3);
+    assert_eq!(app.read(*gpu::DELTA), Some(0.5));
+    assert_eq!(app.read(*gpu::ELAPSED), Some(1.));
+    assert_eq!(app.read(*gpu::STEP_INDEX), Some(2));
+    assert_eq!(app.read(*gpu::DELTA_SUM), Some(1.5));
+}
+
+#[test]
+pub fn use_time_with_real_delta() {
+    let app = App::default()
+        .with_module(gpu::register)
+        .texture((1, 1))
+        .run(1);
+    assert_eq!(app.read(*gpu::DELTA), Some(0.));
+    assert_eq!(app.read(*gpu::ELAPSED), Some(0.));
+    assert_eq!(app.read(*gpu::STEP_INDEX), Some(0));
+    let app = app.run(1);
+    let delta = app.read(*gpu::DELTA).unwrap();
+    assert!(delta > 0.);
+    assert_eq!(app.read(*gpu::ELAPSED), Some(delta));
+    assert_eq!(app.read(*gpu::STEP_INDEX), Some(1));
+}
+
+#[ragna::gpu]
+mod gpu {
+    use ragna::{F32, U32};
+
+    pub(super) static DELTA: F32 = 0.;
+    pub(super) static ELAPSED: F32 = 0.;
+    pub(super) static STEP_INDEX: U32 = 0u;
+    pub(super) static DELTA_SUM: F32 = 0.;
+
+    #[compute]
+    fn run() {
+        *DELTA = ragna::delta_seconds();
+        *ELAPSED = ragna::elapsed_seconds();
+        *STEP_INDEX = ragna::step_index();
+        *DELTA_SUM += ragna::delta_seconds();
+    }
+}
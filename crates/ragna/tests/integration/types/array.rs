@@ -1,6 +1,10 @@
 #![allow(clippy::lossy_float_literal)]
 
-use ragna::App;
+use ragna::{App, Array};
+
+type Nested4<T> = Array<Array<Array<Array<T, 1>, 1>, 1>, 1>;
+type Nested16<T> = Nested4<Nested4<Nested4<Nested4<T>>>>;
+type Nested33<T> = Nested16<Nested16<Array<T, 1>>>;
 
 #[test]
 pub fn use_arrays() {
@@ -16,6 +20,7 @@ pub fn use_arrays() {
         Some([[1, 2], [10, 11], [9, 6], [7, 8]])
     );
     assert_eq!(app.read(*gpu::DEEP), Some([[[[[10]]]]]));
+    assert_eq!(app.read(*gpu::VERY_DEEP_ITEM), Some(10));
     assert_eq!(app.read(*gpu::LENGTH), Some(4));
     assert_eq!(app.read(*gpu::FIRST_ITEM), Some(1));
     assert_eq!(app.read(*gpu::SECOND_ITEM), Some(2));
@@ -31,9 +36,22 @@ pub fn use_arrays() {
     );
 }
 
+#[test]
+pub fn use_array_indexes_in_many_apps() {
+    for _ in 0..1_000 {
+        let _app = App::default().with_module(gpu::register);
+    }
+    let app = App::default()
+        .with_module(gpu::register)
+        .texture((1, 1))
+        .run(1);
+    assert_eq!(app.read(*gpu::NESTED_ARRAY_ITEM), Some(3));
+}
+
 #[ragna::gpu]
 mod gpu {
-    use ragna::{Array, Cpu, Iterable, U32};
+    use super::Nested33;
+    use ragna::{Array, Cpu, Gpu, Iterable, U32};
 
     const CONSTANT: [u32; 4] = [1, 2, 3, 4];
 
@@ -43,6 +61,8 @@ mod gpu {
     pub(super) static NESTED: Array<Array<U32, 2>, 4> = [[1u, 2u], [3u, 4u], [5u, 6u], [7u, 8u]];
     #[allow(clippy::type_complexity)]
     pub(super) static DEEP: Array<Array<Array<Array<Array<U32, 1>, 1>, 1>, 1>, 1> = [[[[[0u]]]]];
+    pub(super) static VERY_DEEP: Nested33<U32> = <Nested33<U32> as Gpu>::Cpu::default().to_gpu();
+    pub(super) static VERY_DEEP_ITEM: U32 = 0u;
     pub(super) static LENGTH: U32 = 0u;
     pub(super) static FIRST_ITEM: U32 = 0u;
     pub(super) static SECOND_ITEM: U32 = 0u;
@@ -61,6 +81,10 @@ mod gpu {
         NESTED[2u][0u] = 9u;
         NESTED[1u] = [10u, 11u];
         DEEP[0u][0u][0u][0u][0u] = 10u;
+        VERY_DEEP[0u][0u][0u][0u][0u][0u][0u][0u][0u][0u][0u][0u][0u][0u][0u][0u][0u][0u][0u][0u]
+            [0u][0u][0u][0u][0u][0u][0u][0u][0u][0u][0u][0u][0u] = 10u;
+        *VERY_DEEP_ITEM = VERY_DEEP[0u][0u][0u][0u][0u][0u][0u][0u][0u][0u][0u][0u][0u][0u][0u][0u]
+            [0u][0u][0u][0u][0u][0u][0u][0u][0u][0u][0u][0u][0u][0u][0u][0u][0u];
         for inner_array in *NESTED {
             for inner_value in *inner_array {
                 *ITER_SUM += *inner_value;
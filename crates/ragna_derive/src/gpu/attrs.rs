@@ -1,8 +1,22 @@
 use syn::{Attribute, Meta};
 
 pub(crate) fn is_compute(attr: &Attribute) -> bool {
+    is_marker(attr, "compute")
+}
+
+pub(crate) fn is_vertex(attr: &Attribute) -> bool {
+    is_marker(attr, "vertex")
+}
+
+pub(crate) fn is_fragment(attr: &Attribute) -> bool {
+    is_marker(attr, "fragment")
+}
+
+pub(crate) fn is_shader_entrypoint(attr: &Attribute) -> bool {
+    is_compute(attr) || is_vertex(attr) || is_fragment(attr)
+}
+
+fn is_marker(attr: &Attribute, name: &str) -> bool {
     let path = attr.meta.path();
-    matches!(attr.meta, Meta::Path(_))
-        && path.segments.len() == 1
-        && path.segments[0].ident == "compute"
+    matches!(attr.meta, Meta::Path(_)) && path.segments.len() == 1 && path.segments[0].ident == name
 }
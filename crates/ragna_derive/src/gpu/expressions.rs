@@ -24,6 +24,10 @@ macro_rules! transform_binary_expr {
 #[allow(clippy::wildcard_enum_match_arm)]
 pub(crate) fn expr_to_gpu(expr: Expr, module: &mut GpuModule) -> Expr {
     match expr {
+        // strings have no GPU equivalent, so they are kept as CPU values (e.g. `Text::push_str`)
+        expr @ Expr::Lit(ExprLit {
+            lit: Lit::Str(_), ..
+        }) => expr,
         Expr::Lit(mut expr) => {
             transform_literal(&mut expr);
             literal_to_gpu(expr)
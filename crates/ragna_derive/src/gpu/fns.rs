@@ -1,6 +1,8 @@
 use crate::gpu::{attrs, GpuModule};
 use syn::spanned::Spanned;
-use syn::{fold, parse_quote_spanned, Block, Expr, FnArg, ItemFn, Pat, Signature, Token, Type};
+use syn::{
+    fold, parse_quote_spanned, Block, Expr, FnArg, ItemFn, Pat, ReturnType, Signature, Token, Type,
+};
 
 pub(crate) fn item_to_gpu(mut item: ItemFn, module: &mut GpuModule) -> ItemFn {
     module.current_fn_signature = Some(item.sig.clone());
@@ -10,11 +12,27 @@ pub(crate) fn item_to_gpu(mut item: ItemFn, module: &mut GpuModule) -> ItemFn {
     if item.attrs.iter().any(attrs::is_compute) {
         module.compute_fns.push(item.sig.ident.clone());
     }
+    if item.attrs.iter().any(attrs::is_vertex) {
+        check_signature(
+            &item.sig,
+            2,
+            "vertex function must have two parameters and a return type",
+            module,
+        );
+    }
+    if item.attrs.iter().any(attrs::is_fragment) {
+        check_signature(
+            &item.sig,
+            1,
+            "fragment function must have one parameter and a return type",
+            module,
+        );
+    }
     let span = item.span();
     item.attrs = item
         .attrs
         .into_iter()
-        .filter(|attr| !attrs::is_compute(attr))
+        .filter(|attr| !attrs::is_shader_entrypoint(attr))
         .chain([parse_quote_spanned! { span => #[allow(unused_braces)] }])
         .collect();
     item = fold::fold_item_fn(module, item);
@@ -23,6 +41,12 @@ pub(crate) fn item_to_gpu(mut item: ItemFn, module: &mut GpuModule) -> ItemFn {
     item
 }
 
+fn check_signature(sig: &Signature, param_count: usize, error: &str, module: &mut GpuModule) {
+    if sig.inputs.len() != param_count || matches!(sig.output, ReturnType::Default) {
+        module.errors.push(syn::Error::new(sig.ident.span(), error));
+    }
+}
+
 pub(crate) fn signature_impl_to_gpu(
     block: &mut Block,
     sig: &mut Signature,
@@ -33,7 +33,7 @@ pub(crate) fn item_to_gpu(mut item: ItemStruct, module: &mut GpuModule) -> Token
                 return quote! {#item};
             }
             fields.named.push(Field {
-                attrs: vec![],
+                attrs: vec![parse_quote_spanned! { span => #[doc(hidden)] }],
                 vis: value_visibility(span, fields),
                 mutability: FieldMutability::None,
                 ident: Some(Ident::new("__value", span)),
@@ -41,7 +41,7 @@ pub(crate) fn item_to_gpu(mut item: ItemStruct, module: &mut GpuModule) -> Token
                 ty: parse_quote_spanned! { span => ::ragna::GpuValue },
             });
         }
-    };
+    }
     item.attrs
         .push(parse_quote_spanned! { span => #[derive(Clone, Copy)] });
     quote_spanned! {
@@ -198,12 +198,15 @@ fn cpu_impl(gpu_struct: &ItemStruct, cpu_struct: &ItemStruct, module: &mut GpuMo
         GenericParam::Type(param) => &param.ident,
         GenericParam::Const(param) => &param.ident,
     });
-    let offset_indexes = (0..=gpu_struct.fields.len())
-        .map(|index| LitInt::new(&index.to_string(), gpu_struct.span()));
-    let field_indexes = (0..gpu_struct.fields.len())
-        .map(|index| LitInt::new(&index.to_string(), gpu_struct.span()));
-    let next_field_indexes = (1..=gpu_struct.fields.len())
-        .map(|index| LitInt::new(&index.to_string(), gpu_struct.span()));
+    let offset_indexes: Vec<_> = (0..=gpu_struct.fields.len())
+        .map(|index| LitInt::new(&index.to_string(), gpu_struct.span()))
+        .collect();
+    let field_indexes: Vec<_> = (0..gpu_struct.fields.len())
+        .map(|index| LitInt::new(&index.to_string(), gpu_struct.span()))
+        .collect();
+    let next_field_indexes: Vec<_> = (1..=gpu_struct.fields.len())
+        .map(|index| LitInt::new(&index.to_string(), gpu_struct.span()))
+        .collect();
     parse_quote_spanned! {
         gpu_struct.span() =>
         impl #impl_generics ::ragna::Cpu for #cpu_ident #type_generics #where_clause {
@@ -221,6 +224,17 @@ fn cpu_impl(gpu_struct: &ItemStruct, cpu_struct: &ItemStruct, module: &mut GpuMo
                 }
             }
 
+            #[allow(clippy::cast_possible_truncation)]
+            fn write_gpu(&self, bytes: &mut [u8]) {
+                let field_offsets = [
+                    #(<Self::Gpu as ::ragna::Gpu>::details().field_offset(#offset_indexes) as usize),*
+                ];
+                #(<#field_types as ::ragna::Cpu>::write_gpu(
+                    &self.#field_idents,
+                    &mut bytes[field_offsets[#field_indexes]..field_offsets[#next_field_indexes]],
+                );)*
+            }
+
             fn to_wgsl(&self) -> ::ragna::Wgsl {
                 ::ragna::Wgsl::Constructor(::ragna::WgslConstructor {
                     type_id: ::std::any::TypeId::of::<Self::Gpu>(),